@@ -0,0 +1,255 @@
+use std::{
+	fs::{
+		self,
+		File,
+	},
+	io::{
+		self,
+		Read,
+	},
+	path::{
+		Component,
+		Path,
+		PathBuf,
+	},
+};
+
+use flate2::read::GzDecoder;
+use sevenz_rust::{
+	Password,
+	SevenZReader,
+};
+use xz2::read::XzDecoder;
+use zip::ZipArchive;
+
+use crate::{
+	Error,
+	Result,
+};
+
+// Nothing we install should come anywhere close to this.
+// It's only here so a broken or malicious archive can't fill up the disk.
+const MAX_EXTRACTED_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+
+// Unix file type bits for symbolic links, as stored in the zip external attributes.
+const UNIX_SYMLINK_MODE: u32 = 0o120_000;
+const UNIX_FILE_TYPE_MASK: u32 = 0o170_000;
+
+// 7z stores unix permissions in the high bits of the Windows attributes when this flag is set.
+const SEVEN_ZIP_UNIX_EXTENSION: u32 = 0x8000;
+const WINDOWS_REPARSE_POINT: u32 = 0x400;
+
+enum ArchiveFormat {
+	Zip,
+	SevenZip,
+	Tar,
+	TarGz,
+	TarXz,
+	TarZst,
+}
+
+// File extensions can't be trusted (lots of urls don't even have one), so we look at the first bytes instead.
+fn detect_format(archive_path: &Path) -> Result<ArchiveFormat> {
+	let mut header = Vec::new();
+	File::open(archive_path)?
+		.take(512)
+		.read_to_end(&mut header)?;
+
+	if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+		Ok(ArchiveFormat::Zip)
+	} else if header.starts_with(&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C]) {
+		Ok(ArchiveFormat::SevenZip)
+	} else if header.starts_with(&[0x1F, 0x8B]) {
+		Ok(ArchiveFormat::TarGz)
+	} else if header.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+		Ok(ArchiveFormat::TarXz)
+	} else if header.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+		Ok(ArchiveFormat::TarZst)
+	} else if header.get(257..262) == Some(b"ustar".as_slice()) {
+		Ok(ArchiveFormat::Tar)
+	} else {
+		Err(Error::UnsupportedArchiveFormat(archive_path.to_path_buf()))
+	}
+}
+
+// Same idea as zip's enclosed_name, for formats that don't have it.
+fn get_enclosed_path(entry_name: &str) -> Option<PathBuf> {
+	// Archives made on Windows can use backslashes as separators.
+	let path = PathBuf::from(entry_name.replace('\\', "/"));
+
+	let is_enclosed = path
+		.components()
+		.all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+	(is_enclosed && path.components().next().is_some()).then_some(path)
+}
+
+// Keeps track of what's been written, so every format goes through the same checks.
+struct Extractor<'a> {
+	archive_path: &'a Path,
+	target_path: &'a Path,
+	written_files: Vec<PathBuf>,
+	total_size: u64,
+}
+
+impl<'a> Extractor<'a> {
+	const fn new(archive_path: &'a Path, target_path: &'a Path) -> Self {
+		Self {
+			archive_path,
+			target_path,
+			written_files: Vec::new(),
+			total_size: 0,
+		}
+	}
+
+	fn unsafe_entry_error(&self, entry_name: &str) -> Error {
+		Error::UnsafeArchiveEntry(self.archive_path.to_path_buf(), entry_name.to_string())
+	}
+
+	fn get_output_path(&self, entry_name: &str, relative_path: Option<PathBuf>) -> Result<PathBuf> {
+		relative_path
+			.map(|relative_path| self.target_path.join(relative_path))
+			.ok_or_else(|| self.unsafe_entry_error(entry_name))
+	}
+
+	fn write_file(&mut self, output_path: PathBuf, reader: &mut dyn Read) -> Result {
+		if let Some(parent) = output_path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		// The sizes in the archive can't be trusted, so we count what actually gets written.
+		let remaining_size = MAX_EXTRACTED_SIZE - self.total_size;
+		let mut output_file = File::create(&output_path)?;
+		let written_size = io::copy(&mut reader.take(remaining_size + 1), &mut output_file)?;
+
+		self.total_size += written_size;
+		self.written_files.push(output_path);
+
+		if self.total_size > MAX_EXTRACTED_SIZE {
+			return Err(Error::ArchiveTooLarge(
+				self.archive_path.to_path_buf(),
+				MAX_EXTRACTED_SIZE,
+			));
+		}
+
+		Ok(())
+	}
+
+	fn extract_zip(&mut self) -> Result {
+		let mut archive = ZipArchive::new(File::open(self.archive_path)?)?;
+
+		for index in 0..archive.len() {
+			let mut entry = archive.by_index(index)?;
+
+			if entry.unix_mode().map_or(false, |mode| {
+				mode & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_MODE
+			}) {
+				return Err(self.unsafe_entry_error(entry.name()));
+			}
+
+			// enclosed_name is None for absolute paths and paths that escape the target with `..`.
+			let output_path =
+				self.get_output_path(entry.name(), entry.enclosed_name().map(Path::to_path_buf))?;
+
+			if entry.is_dir() {
+				fs::create_dir_all(&output_path)?;
+			} else {
+				self.write_file(output_path, &mut entry)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn extract_seven_zip(&mut self) -> Result {
+		let archive_file = File::open(self.archive_path)?;
+		let archive_size = archive_file.metadata()?.len();
+		let mut archive = SevenZReader::new(archive_file, archive_size, Password::empty())?;
+
+		// The 7z callback can only return 7z errors, so ours are kept here until it stops.
+		let mut entry_error: Option<Error> = None;
+
+		archive.for_each_entries(|entry, entry_reader| {
+			let result = (|| {
+				if entry.is_anti_item {
+					return Ok(());
+				}
+
+				let attributes = entry.windows_attributes;
+				let is_symlink = (attributes & WINDOWS_REPARSE_POINT != 0)
+					|| (attributes & SEVEN_ZIP_UNIX_EXTENSION != 0
+						&& (attributes >> 16) & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_MODE);
+
+				if entry.has_windows_attributes && is_symlink {
+					return Err(self.unsafe_entry_error(&entry.name));
+				}
+
+				let output_path =
+					self.get_output_path(&entry.name, get_enclosed_path(&entry.name))?;
+
+				if entry.is_directory {
+					Ok(fs::create_dir_all(&output_path)?)
+				} else {
+					self.write_file(output_path, entry_reader)
+				}
+			})();
+
+			result.map_or_else(
+				|error| {
+					entry_error = Some(error);
+					Ok(false)
+				},
+				|()| Ok(true),
+			)
+		})?;
+
+		entry_error.map_or(Ok(()), Err)
+	}
+
+	fn extract_tar<TReader: Read>(&mut self, reader: TReader) -> Result {
+		let mut archive = tar::Archive::new(reader);
+
+		for entry_result in archive.entries()? {
+			let mut entry = entry_result?;
+			let entry_name = entry.path()?.to_string_lossy().to_string();
+			let entry_type = entry.header().entry_type();
+
+			if entry_type.is_symlink() || entry_type.is_hard_link() {
+				return Err(self.unsafe_entry_error(&entry_name));
+			}
+
+			if entry_type.is_dir() {
+				let output_path =
+					self.get_output_path(&entry_name, get_enclosed_path(&entry_name))?;
+				fs::create_dir_all(&output_path)?;
+			} else if entry_type.is_file() || entry_type.is_contiguous() {
+				let output_path =
+					self.get_output_path(&entry_name, get_enclosed_path(&entry_name))?;
+				self.write_file(output_path, &mut entry)?;
+			}
+			// Anything else is metadata (pax headers and such), or something we don't want to extract.
+		}
+
+		Ok(())
+	}
+}
+
+// Extracts the archive into `target_path`, refusing to write anything outside of it.
+// Supports zip, 7z, and tar (plain, gz, xz or zst).
+// Returns the paths of all the files that were written.
+pub fn extract(archive_path: &Path, target_path: &Path) -> Result<Vec<PathBuf>> {
+	let mut extractor = Extractor::new(archive_path, target_path);
+
+	match detect_format(archive_path)? {
+		ArchiveFormat::Zip => extractor.extract_zip()?,
+		ArchiveFormat::SevenZip => extractor.extract_seven_zip()?,
+		ArchiveFormat::Tar => extractor.extract_tar(File::open(archive_path)?)?,
+		ArchiveFormat::TarGz => extractor.extract_tar(GzDecoder::new(File::open(archive_path)?))?,
+		ArchiveFormat::TarXz => extractor.extract_tar(XzDecoder::new(File::open(archive_path)?))?,
+		ArchiveFormat::TarZst => {
+			extractor.extract_tar(zstd::stream::read::Decoder::new(File::open(archive_path)?)?)?;
+		}
+	}
+
+	Ok(extractor.written_files)
+}
@@ -0,0 +1,189 @@
+use std::{
+	collections::{
+		HashMap,
+		HashSet,
+	},
+	fs,
+	path::PathBuf,
+};
+
+use log::{
+	info,
+	warn,
+};
+
+use crate::{
+	files::get_dir_size,
+	game_locations,
+	install_journal,
+	installed_game,
+	paths::{
+		self,
+		legacy_hash_path,
+	},
+	serializable_struct,
+	Result,
+};
+
+serializable_struct!(DiskUsage {
+	pub path: PathBuf,
+	pub size: u64,
+});
+
+serializable_struct!(DiskUsageReport {
+	// Keys are installed game ids.
+	pub games: HashMap<String, DiskUsage>,
+	// Keys are mod loader ids.
+	pub mod_loaders: HashMap<String, DiskUsage>,
+	pub downloads: HashMap<String, DiskUsage>,
+	// Older versions of mods, kept around for rolling back.
+	pub mod_versions: HashMap<String, DiskUsage>,
+	// Mod data folders that don't belong to any game we know of. Keys are the folder names.
+	pub orphaned_games: HashMap<String, DiskUsage>,
+	// Same as above, but the journal says there are still files to restore in the game folder.
+	// These are never removed, since we'd lose the only copy of the game's original files.
+	pub orphaned_games_with_journal: HashMap<String, DiskUsage>,
+});
+
+type UsageMap = HashMap<String, DiskUsage>;
+
+impl DiskUsage {
+	fn new(path: PathBuf) -> Self {
+		Self {
+			size: get_dir_size(&path),
+			path,
+		}
+	}
+}
+
+fn get_mod_loaders_usage() -> Result<(UsageMap, UsageMap, UsageMap)> {
+	let mut mod_loaders = HashMap::new();
+	let mut downloads = HashMap::new();
+	let mut mod_versions = HashMap::new();
+
+	let mod_loaders_path = paths::installed_mods_path()?;
+	if !mod_loaders_path.is_dir() {
+		return Ok((mod_loaders, downloads, mod_versions));
+	}
+
+	for entry in fs::read_dir(mod_loaders_path)?.flatten() {
+		if !entry.file_type()?.is_dir() {
+			continue;
+		}
+
+		let mod_loader_id = entry.file_name().to_string_lossy().to_string();
+
+		mod_loaders.insert(
+			mod_loader_id.clone(),
+			DiskUsage::new(entry.path().join("mods")),
+		);
+		downloads.insert(
+			mod_loader_id.clone(),
+			DiskUsage::new(entry.path().join("downloads")),
+		);
+		mod_versions.insert(mod_loader_id, DiskUsage::new(entry.path().join("versions")));
+	}
+
+	Ok((mod_loaders, downloads, mod_versions))
+}
+
+// Any id we might still need: current games, games in drives that aren't connected right now,
+// and the old ids of current games, in case moving their data to the new id failed.
+fn get_known_game_ids(installed_games: &installed_game::Map) -> Result<HashSet<String>> {
+	let mut known_ids = game_locations::get_known_game_ids()?;
+
+	for game in installed_games.values() {
+		known_ids.insert(game.id.clone());
+		known_ids.insert(legacy_hash_path(&game.executable.path));
+	}
+
+	Ok(known_ids)
+}
+
+// Returns the removable orphaned folders, and the ones that still have a journal.
+fn get_orphaned_game_folders(
+	installed_games: &installed_game::Map,
+) -> Result<(UsageMap, UsageMap)> {
+	let mut orphaned_folders = HashMap::new();
+	let mut journaled_folders = HashMap::new();
+
+	let installed_mods_root = installed_game::get_installed_mods_root()?;
+	if !installed_mods_root.is_dir() {
+		return Ok((orphaned_folders, journaled_folders));
+	}
+
+	let known_ids = get_known_game_ids(installed_games)?;
+
+	for entry in fs::read_dir(installed_mods_root)?.flatten() {
+		// Symlinks and files are left alone, we only ever create folders in here.
+		if !entry.file_type()?.is_dir() {
+			continue;
+		}
+
+		let folder_name = entry.file_name().to_string_lossy().to_string();
+		if known_ids.contains(&folder_name) {
+			continue;
+		}
+
+		// If we can't read the journal, better to assume it has something in it.
+		if install_journal::has_entries(&entry.path()).unwrap_or(true) {
+			journaled_folders.insert(folder_name, DiskUsage::new(entry.path()));
+		} else {
+			orphaned_folders.insert(folder_name, DiskUsage::new(entry.path()));
+		}
+	}
+
+	Ok((orphaned_folders, journaled_folders))
+}
+
+pub fn get_report(installed_games: &installed_game::Map) -> Result<DiskUsageReport> {
+	let (mod_loaders, downloads, mod_versions) = get_mod_loaders_usage()?;
+	let (orphaned_games, orphaned_games_with_journal) = get_orphaned_game_folders(installed_games)?;
+
+	Ok(DiskUsageReport {
+		games: installed_games
+			.values()
+			.filter_map(|game| {
+				let installed_mods_folder = installed_game::get_installed_mods_root()
+					.ok()?
+					.join(&game.id);
+
+				installed_mods_folder
+					.is_dir()
+					.then(|| (game.id.clone(), DiskUsage::new(installed_mods_folder)))
+			})
+			.collect(),
+		mod_loaders,
+		downloads,
+		mod_versions,
+		orphaned_games,
+		orphaned_games_with_journal,
+	})
+}
+
+// Only removes the given folders, which should come from the orphaned games in a report the user has seen.
+// Folders that aren't removable orphans anymore are skipped.
+// Returns the folders that were removed.
+pub fn remove_orphaned_game_folders(
+	installed_games: &installed_game::Map,
+	folder_ids: &[String],
+) -> Result<Vec<DiskUsage>> {
+	let (mut orphaned_folders, _) = get_orphaned_game_folders(installed_games)?;
+	let mut removed_folders = Vec::new();
+
+	for folder_id in folder_ids {
+		let Some(orphaned_folder) = orphaned_folders.remove(folder_id) else {
+			warn!("Not removing mod data folder `{folder_id}`, since it isn't orphaned anymore");
+			continue;
+		};
+
+		info!(
+			"Removing orphaned mod data folder `{}`",
+			orphaned_folder.path.display()
+		);
+		fs::remove_dir_all(&orphaned_folder.path)?;
+		removed_folders.push(orphaned_folder);
+	}
+
+	Ok(removed_folders)
+}
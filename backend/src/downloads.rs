@@ -0,0 +1,253 @@
+use std::{
+	collections::HashMap,
+	fs::{
+		self,
+		OpenOptions,
+	},
+	io::{
+		self,
+		Write,
+	},
+	path::{
+		Path,
+		PathBuf,
+	},
+	sync::{
+		atomic::{
+			AtomicBool,
+			Ordering,
+		},
+		Arc,
+		Mutex,
+	},
+	time::{
+		Duration,
+		Instant,
+	},
+};
+
+use lazy_static::lazy_static;
+use log::{
+	error,
+	info,
+};
+use reqwest::{
+	header::RANGE,
+	StatusCode,
+};
+use sha2::{
+	Digest,
+	Sha256,
+};
+
+use crate::{
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// Chunks can be tiny, so sending an event for each one would flood the frontend.
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(100);
+
+serializable_struct!(DownloadProgress {
+	pub id: String,
+	pub downloaded_bytes: u64,
+	pub total_bytes: Option<u64>,
+});
+
+lazy_static! {
+	// Cancellation flags for the downloads currently in progress, keyed by download id.
+	static ref ACTIVE_DOWNLOADS: Mutex<HashMap<String, Arc<AtomicBool>>> = Mutex::default();
+}
+
+// Removes the download from the active list when it ends, however it ends.
+struct ActiveDownload {
+	id: String,
+	cancelled: Arc<AtomicBool>,
+}
+
+impl ActiveDownload {
+	fn start(id: &str) -> Result<Self> {
+		let mut active_downloads = ACTIVE_DOWNLOADS
+			.lock()
+			.map_err(|err| Error::FailedToAccessStateData(err.to_string()))?;
+
+		if active_downloads.contains_key(id) {
+			return Err(Error::DownloadInProgress(id.to_string()));
+		}
+
+		let cancelled = Arc::new(AtomicBool::new(false));
+		active_downloads.insert(id.to_string(), Arc::clone(&cancelled));
+		drop(active_downloads);
+
+		Ok(Self {
+			id: id.to_string(),
+			cancelled,
+		})
+	}
+
+	fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+}
+
+impl Drop for ActiveDownload {
+	fn drop(&mut self) {
+		if let Ok(mut active_downloads) = ACTIVE_DOWNLOADS.lock() {
+			active_downloads.remove(&self.id);
+		}
+	}
+}
+
+pub fn cancel(id: &str) -> Result {
+	ACTIVE_DOWNLOADS
+		.lock()
+		.map_err(|err| Error::FailedToAccessStateData(err.to_string()))?
+		.get(id)
+		.ok_or_else(|| Error::DataEntryNotFound(id.to_string()))?
+		.store(true, Ordering::Relaxed);
+
+	Ok(())
+}
+
+fn get_verification_error(
+	path: &Path,
+	sha256: Option<&str>,
+	size: Option<u64>,
+) -> Result<Option<String>> {
+	if let Some(expected_size) = size {
+		let actual_size = fs::metadata(path)?.len();
+		if actual_size != expected_size {
+			return Ok(Some(format!(
+				"expected {expected_size} bytes, got {actual_size}"
+			)));
+		}
+	}
+
+	if let Some(expected_hash) = sha256 {
+		let mut hasher = Sha256::new();
+		io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+		let actual_hash = format!("{:x}", hasher.finalize());
+
+		if !actual_hash.eq_ignore_ascii_case(expected_hash.trim()) {
+			return Ok(Some(format!(
+				"expected SHA-256 {expected_hash}, got {actual_hash}"
+			)));
+		}
+	}
+
+	Ok(None)
+}
+
+// Checks a downloaded file against the size and hash we expected, if we know them.
+// Files that don't match get deleted, so they can't be used by accident later.
+pub fn verify_file(path: &Path, sha256: Option<&str>, size: Option<u64>) -> Result {
+	if let Some(verification_error) = get_verification_error(path, sha256, size)? {
+		if let Err(err) = fs::remove_file(path) {
+			error!(
+				"Failed to remove file `{}` that failed verification: {err}",
+				path.display()
+			);
+		}
+
+		return Err(Error::DownloadVerificationFailed(
+			path.to_path_buf(),
+			verification_error,
+		));
+	}
+
+	Ok(())
+}
+
+// The partial file name depends on the url, so we don't try to resume a download using a different file.
+pub fn get_partial_path(folder: &Path, id: &str, url: &str) -> PathBuf {
+	let url_hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+
+	folder.join(format!("{id}.{}.part", &url_hash[..16]))
+}
+
+// Streams the file to `partial_path` and only moves it to `target_path` once it's complete.
+// If `partial_path` already has something in it from an interrupted download, we try to resume it.
+// Cancelled downloads have their partial file deleted, since the user probably doesn't want it anymore.
+pub async fn download_file<F>(
+	id: &str,
+	url: &str,
+	partial_path: &Path,
+	target_path: &Path,
+	progress_handler: F,
+) -> Result
+where
+	F: Fn(DownloadProgress) + Send + Sync,
+{
+	let active_download = ActiveDownload::start(id)?;
+
+	let existing_bytes = fs::metadata(partial_path).map_or(0, |metadata| metadata.len());
+
+	let mut request = reqwest::Client::new().get(url);
+	if existing_bytes > 0 {
+		request = request.header(RANGE, format!("bytes={existing_bytes}-"));
+	}
+
+	let mut response = request.send().await?;
+
+	if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+		// Probably means the partial file is bigger than the file we're trying to get,
+		// so it's not the same file. Gotta start over.
+		fs::remove_file(partial_path)?;
+		response = reqwest::get(url).await?;
+	}
+
+	let mut response = response.error_for_status()?;
+
+	let resuming = response.status() == StatusCode::PARTIAL_CONTENT;
+	let mut downloaded_bytes = if resuming { existing_bytes } else { 0 };
+	let total_bytes = response
+		.content_length()
+		.map(|content_length| content_length + downloaded_bytes);
+
+	if resuming {
+		info!("Resuming download `{id}` from byte {existing_bytes}");
+	}
+
+	let mut file = OpenOptions::new()
+		.create(true)
+		.write(true)
+		.append(resuming)
+		.truncate(!resuming)
+		.open(partial_path)?;
+
+	let send_progress = |bytes| {
+		progress_handler(DownloadProgress {
+			id: id.to_string(),
+			downloaded_bytes: bytes,
+			total_bytes,
+		});
+	};
+	let mut last_progress_time = Instant::now();
+
+	while let Some(chunk) = response.chunk().await? {
+		if active_download.is_cancelled() {
+			drop(file);
+			fs::remove_file(partial_path)?;
+			return Err(Error::DownloadCancelled(id.to_string()));
+		}
+
+		file.write_all(&chunk)?;
+		downloaded_bytes += u64::try_from(chunk.len()).unwrap_or_default();
+
+		if last_progress_time.elapsed() >= PROGRESS_EVENT_INTERVAL {
+			send_progress(downloaded_bytes);
+			last_progress_time = Instant::now();
+		}
+	}
+
+	file.flush()?;
+	drop(file);
+
+	// Always let the frontend know where we ended, even if the last chunks got throttled.
+	send_progress(downloaded_bytes);
+
+	fs::rename(partial_path, target_path)?;
+
+	Ok(())
+}
@@ -0,0 +1,136 @@
+use std::{
+	fs::File,
+	io::Read,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use base64::engine::general_purpose;
+
+use crate::{
+	serializable_struct,
+	Result,
+};
+
+serializable_struct!(EpicCatalogCategory { pub path: String });
+
+serializable_struct!(EpicCatalogReleaseInfo {
+	pub app_id: String,
+	pub platform: Vec<String>,
+	pub date_added: Option<String>,
+});
+
+serializable_struct!(EpicCatalogImage {
+	pub height: i32,
+	pub url: String,
+});
+
+serializable_struct!(EpicCatalogItem {
+	pub id: String,
+	pub namespace: String,
+	pub title: String,
+	pub categories: Vec<EpicCatalogCategory>,
+	pub release_info: Vec<EpicCatalogReleaseInfo>,
+	pub key_images: Vec<EpicCatalogImage>,
+});
+
+impl EpicCatalogItem {
+	pub fn get_release_date(&self) -> Option<i64> {
+		Some(
+			self.release_info
+				.first()?
+				.date_added
+				.as_ref()?
+				.parse::<chrono::DateTime<chrono::Utc>>()
+				.ok()?
+				.timestamp(),
+		)
+	}
+
+	pub fn get_thumbnail_url(&self) -> Option<String> {
+		Some(
+			self.key_images
+				.iter()
+				// The available images here seem to be a bit inconsistent, so we'll just pick the smallest one.
+				.min_by_key(|image| image.height)?
+				.url
+				.clone(),
+		)
+	}
+
+	pub fn is_game(&self) -> bool {
+		self.categories
+			.iter()
+			.any(|category| category.path == "games")
+	}
+}
+
+// The catalog cache is a base64 encoded json array.
+pub fn parse<R: Read>(reader: &mut R) -> Result<Vec<EpicCatalogItem>> {
+	let mut decoder = base64::read::DecoderReader::new(reader, &general_purpose::STANDARD);
+	let mut json = String::default();
+	decoder.read_to_string(&mut json)?;
+
+	Ok(serde_json::from_str::<Vec<EpicCatalogItem>>(&json)?)
+}
+
+fn get_path(app_data_path: &Path) -> PathBuf {
+	app_data_path.join("Catalog").join("catcache.bin")
+}
+
+pub fn read(app_data_path: &Path) -> Result<Vec<EpicCatalogItem>> {
+	parse(&mut File::open(get_path(app_data_path))?)
+}
+
+#[cfg(test)]
+mod tests {
+	use base64::Engine;
+
+	use super::*;
+
+	const CATALOG_JSON: &str = r#"[
+		{
+			"id": "item-id",
+			"namespace": "game-namespace",
+			"title": "Some Game",
+			"categories": [{ "path": "applications" }, { "path": "games" }],
+			"releaseInfo": [
+				{
+					"appId": "SomeGame",
+					"platform": ["Windows"],
+					"dateAdded": "2021-05-20T16:00:00.000Z"
+				}
+			],
+			"keyImages": [
+				{ "height": 1080, "url": "https://example.com/big.jpg" },
+				{ "height": 128, "url": "https://example.com/small.jpg" }
+			]
+		}
+	]"#;
+
+	#[test]
+	fn parses_base64_catalog() -> Result {
+		let encoded = general_purpose::STANDARD.encode(CATALOG_JSON);
+		let items = parse(&mut encoded.as_bytes())?;
+
+		assert_eq!(items.len(), 1);
+		let item = &items[0];
+		assert_eq!(item.namespace, "game-namespace");
+		assert_eq!(item.title, "Some Game");
+		assert!(item.is_game());
+		assert_eq!(item.get_release_date(), Some(1_621_526_400));
+		assert_eq!(
+			item.get_thumbnail_url().as_deref(),
+			Some("https://example.com/small.jpg")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn rejects_catalog_that_is_not_base64() {
+		assert!(parse(&mut CATALOG_JSON.as_bytes()).is_err());
+	}
+}
@@ -0,0 +1,99 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	paths::glob_path,
+	serializable_struct,
+	Result,
+};
+
+serializable_struct!(EpicManifest {
+	#[serde(rename = "DisplayName")]
+	pub display_name: String,
+	#[serde(rename = "LaunchExecutable")]
+	pub launch_executable: String,
+	#[serde(rename = "InstallLocation")]
+	pub install_location: String,
+	#[serde(rename = "CatalogNamespace")]
+	pub catalog_namespace: String,
+	#[serde(rename = "CatalogItemId")]
+	pub catalog_item_id: String,
+	#[serde(rename = "AppName")]
+	pub app_name: String,
+});
+
+impl EpicManifest {
+	pub fn get_executable_path(&self) -> PathBuf {
+		PathBuf::from(&self.install_location).join(&self.launch_executable)
+	}
+}
+
+pub fn parse(json: &str) -> Result<EpicManifest> {
+	Ok(serde_json::from_str::<EpicManifest>(json)?)
+}
+
+pub fn read(path: &Path) -> Result<EpicManifest> {
+	parse(&fs::read_to_string(path)?)
+}
+
+// Reads every *.item manifest from the launcher's app data folder.
+// Manifests that fail to parse are logged and skipped.
+pub fn read_all(app_data_path: &Path) -> Vec<EpicManifest> {
+	glob_path(&app_data_path.join("Manifests").join("*.item"))
+		.iter()
+		.filter_map(|manifest_path| match read(manifest_path) {
+			Ok(manifest) => Some(manifest),
+			Err(err) => {
+				error!(
+					"Failed to parse Epic manifest `{}`: {err}",
+					manifest_path.display()
+				);
+				None
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_item_manifest() -> Result {
+		// Trimmed down .item file, the launcher writes a lot more fields we don't care about.
+		let manifest = parse(
+			r#"{
+				"FormatVersion": 0,
+				"DisplayName": "Some Game",
+				"LaunchExecutable": "Binaries/Win64/SomeGame.exe",
+				"InstallLocation": "C:/Games/SomeGame",
+				"CatalogNamespace": "game-namespace",
+				"CatalogItemId": "item-id",
+				"AppName": "SomeGame"
+			}"#,
+		)?;
+
+		assert_eq!(manifest.display_name, "Some Game");
+		assert_eq!(manifest.catalog_namespace, "game-namespace");
+		assert_eq!(manifest.catalog_item_id, "item-id");
+		assert_eq!(manifest.app_name, "SomeGame");
+		assert_eq!(
+			manifest.get_executable_path(),
+			PathBuf::from("C:/Games/SomeGame").join("Binaries/Win64/SomeGame.exe")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn rejects_manifest_with_missing_fields() {
+		assert!(parse(r#"{ "DisplayName": "Some Game" }"#).is_err());
+	}
+}
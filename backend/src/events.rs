@@ -16,6 +16,8 @@ serializable_enum!(AppEvent {
 	ExecutedProviderCommand,
 	GameAdded,
 	GameRemoved,
+	ModDownloadProgress,
+	ModUpdatesFinished,
 	Error,
 });
 
@@ -1,20 +1,68 @@
 use std::{
 	fs,
-	path::Path,
+	path::{
+		Path,
+		PathBuf,
+	},
 };
 
+use log::warn;
+
 use crate::Result;
 
-pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result {
+// Returns the paths of all the files that were written.
+// Symlinks are skipped, we don't want to copy whatever they point to.
+pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+	copy_dir_all_with(src, dst, &mut |_| Ok(()))
+}
+
+// Same as copy_dir_all, but calls `before_write` with each target path before writing to it.
+pub fn copy_dir_all_with<F>(
+	src: impl AsRef<Path>,
+	dst: impl AsRef<Path>,
+	before_write: &mut F,
+) -> Result<Vec<PathBuf>>
+where
+	F: FnMut(&Path) -> Result,
+{
+	let mut written_files = Vec::new();
+
 	fs::create_dir_all(&dst)?;
 	for entry in fs::read_dir(src)? {
 		let entry = entry?;
 		let file_type = entry.file_type()?;
+		let target_path = dst.as_ref().join(entry.file_name());
 		if file_type.is_dir() {
-			copy_dir_all(entry.path(), dst.as_ref().join(entry.file_name()))?;
+			written_files.extend(copy_dir_all_with(entry.path(), target_path, before_write)?);
+		} else if file_type.is_file() {
+			before_write(&target_path)?;
+			fs::copy(entry.path(), &target_path)?;
+			written_files.push(target_path);
 		} else {
-			fs::copy(entry.path(), dst.as_ref().join(entry.file_name()))?;
+			warn!("Skipping symlink `{}`", entry.path().display());
 		}
 	}
-	Ok(())
+
+	Ok(written_files)
+}
+
+// Doesn't follow symlinks, and ignores anything it fails to read.
+pub fn get_dir_size(path: impl AsRef<Path>) -> u64 {
+	let Ok(entries) = fs::read_dir(path) else {
+		return 0;
+	};
+
+	entries
+		.filter_map(|entry| {
+			let entry = entry.ok()?;
+			let file_type = entry.file_type().ok()?;
+			if file_type.is_dir() {
+				Some(get_dir_size(entry.path()))
+			} else if file_type.is_file() {
+				Some(entry.metadata().ok()?.len())
+			} else {
+				None
+			}
+		})
+		.sum()
 }
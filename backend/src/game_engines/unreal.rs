@@ -178,7 +178,7 @@ fn is_valid_win_folder(path: &Path) -> bool {
 // Some games have multiple exes in the same folder.
 // The exe names can be anything, but it's common to have a launcher exe,
 // next to a *-Shipping.exe, which is usually the one we want.
-fn is_shipping_exe(path: &Path) -> bool {
+pub fn is_shipping_exe(path: &Path) -> bool {
 	path.file_name()
 		.and_then(|file_name| file_name.to_str())
 		.is_some_and(|file_name| file_name.ends_with("Shipping.exe"))
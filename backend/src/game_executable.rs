@@ -1,5 +1,9 @@
 use std::{
-	fs,
+	fs::{
+		self,
+		File,
+	},
+	io::Read,
 	path::{
 		Path,
 		PathBuf,
@@ -8,7 +12,10 @@ use std::{
 
 use goblin::{
 	elf::Elf,
-	pe::PE,
+	pe::{
+		header::Header,
+		PE,
+	},
 };
 use log::error;
 
@@ -62,6 +69,24 @@ pub fn read_linux_binary(file: &[u8]) -> Result<(Option<OperatingSystem>, Option
 	}
 }
 
+// Subsystem values from the PE optional header.
+// Goblin doesn't define these, so we need our own.
+const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+
+// Tells apart windowed programs (most games) from console programs (servers, tools, etc).
+// Only the beginning of the file is read, since game exes can be huge.
+pub fn is_windows_gui_binary(file_path: &Path) -> Result<bool> {
+	let mut header_bytes = vec![0u8; 4096];
+	let bytes_read = File::open(file_path)?.read(&mut header_bytes)?;
+	header_bytes.truncate(bytes_read);
+
+	Ok(Header::parse(&header_bytes)?
+		.optional_header
+		.is_some_and(|optional_header| {
+			optional_header.windows_fields.subsystem == IMAGE_SUBSYSTEM_WINDOWS_GUI
+		}))
+}
+
 pub fn get_os_and_architecture(
 	file_path: &Path,
 ) -> Result<(Option<OperatingSystem>, Option<Architecture>)> {
@@ -0,0 +1,156 @@
+use std::{
+	collections::{
+		HashMap,
+		HashSet,
+	},
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::{
+	error,
+	info,
+};
+
+use crate::{
+	installed_game::{
+		self,
+		InstalledGame,
+	},
+	paths::{
+		app_data_path,
+		legacy_hash_path,
+	},
+	serializable_struct,
+	Result,
+};
+
+// Remembers where we last saw each installed game, so we can tell when a game moves.
+// For instance, when moving a game to another Steam library folder.
+serializable_struct!(GameLocation {
+	pub id: String,
+	pub executable_path: PathBuf,
+});
+
+// Keys are owned game ids. An owned game can have more than one installed game.
+type Map = HashMap<String, Vec<GameLocation>>;
+
+fn locations_path() -> Result<PathBuf> {
+	Ok(app_data_path()?.join("game-locations.json"))
+}
+
+fn read_locations(locations_path: &Path) -> Result<Map> {
+	if !locations_path.is_file() {
+		return Ok(Map::default());
+	}
+
+	let json = fs::read_to_string(locations_path)?;
+	Ok(serde_json::from_str::<Map>(&json)?)
+}
+
+fn write_locations(locations: &Map) -> Result {
+	fs::write(locations_path()?, serde_json::to_string_pretty(locations)?)?;
+
+	Ok(())
+}
+
+// Ids of every installed game we've ever seen, including the ones that aren't currently found.
+pub fn get_known_game_ids() -> Result<HashSet<String>> {
+	Ok(read_locations(&locations_path()?)?
+		.into_values()
+		.flatten()
+		.flat_map(|location| [legacy_hash_path(&location.executable_path), location.id])
+		.collect())
+}
+
+// A game counts as moved if the same owned game used to have an executable with the same name,
+// in a path that doesn't exist anymore.
+fn find_previous_location<'a>(
+	game: &InstalledGame,
+	previous_locations: &'a [GameLocation],
+) -> Option<&'a GameLocation> {
+	let file_name = game.executable.path.file_name()?;
+
+	previous_locations.iter().find(|location| {
+		location.id != game.id
+			&& location.executable_path.file_name() == Some(file_name)
+			&& !location.executable_path.exists()
+	})
+}
+
+// Moves the mod data of games that changed location, and remembers the current locations for next time.
+// Returns the id changes as (previous id, new id) pairs.
+pub fn relink_moved_games(installed_games: &mut [InstalledGame]) -> Vec<(String, String)> {
+	let mut locations = match locations_path().and_then(|path| read_locations(&path)) {
+		Ok(locations) => locations,
+		Err(err) => {
+			error!("Failed to read game locations: {err}");
+			Map::default()
+		}
+	};
+
+	let mut id_changes = Vec::new();
+	let mut current_locations = Map::default();
+
+	for game in installed_games.iter_mut() {
+		let Some(owned_game_id) = game.owned_game_id.clone() else {
+			continue;
+		};
+
+		if let Some(previous_location) = locations
+			.get(&owned_game_id)
+			.and_then(|previous_locations| find_previous_location(game, previous_locations))
+		{
+			info!(
+				"Game {} moved from `{}` to `{}`",
+				game.name,
+				previous_location.executable_path.display(),
+				game.executable.path.display()
+			);
+
+			match installed_game::get_installed_mods_root().and_then(|installed_mods_root| {
+				game.move_installed_mods_folder(&installed_mods_root.join(&previous_location.id))
+			}) {
+				Ok(()) => {
+					id_changes.push((previous_location.id.clone(), game.id.clone()));
+					game.refresh_installed_mods();
+				}
+				Err(err) => error!("Failed to move mods for game {}: {err}", game.name),
+			}
+		}
+
+		current_locations
+			.entry(owned_game_id)
+			.or_insert_with(Vec::new)
+			.push(GameLocation {
+				id: game.id.clone(),
+				executable_path: game.executable.path.clone(),
+			});
+	}
+
+	// Games that weren't found this time are kept, since they might just be in a drive that isn't connected.
+	// Only the ones that were found again, or that moved somewhere else, get replaced.
+	for (owned_game_id, game_locations) in current_locations {
+		let merged_locations = locations.entry(owned_game_id).or_default();
+
+		merged_locations.retain(|previous_location| {
+			!game_locations.iter().any(|location| {
+				location.id == previous_location.id
+					|| location.executable_path == previous_location.executable_path
+			}) && !id_changes
+				.iter()
+				.any(|(previous_id, _)| *previous_id == previous_location.id)
+		});
+
+		merged_locations.extend(game_locations);
+	}
+
+	if let Err(err) = write_locations(&locations) {
+		error!("Failed to save game locations: {err}");
+	}
+
+	id_changes
+}
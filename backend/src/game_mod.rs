@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate::{
 	game_engines::{
 		game_engine::GameEngineBrand,
@@ -12,3 +14,60 @@ serializable_struct!(CommonModData {
 	pub unity_backend: Option<UnityScriptingBackend>,
 	pub loader_id: String, // TODO make enum
 });
+
+// Points to another mod, optionally with a version requirement like ">=1.2" or "^2".
+serializable_struct!(ModRequirement {
+	pub id: String,
+	pub version: Option<String>,
+});
+
+impl ModRequirement {
+	pub fn is_met_by(&self, version: &str) -> bool {
+		let Some(requirement) = &self.version else {
+			return true;
+		};
+
+		match (
+			semver::VersionReq::parse(requirement),
+			parse_mod_version(version),
+		) {
+			(Ok(version_requirement), Some(parsed_version)) => {
+				version_requirement.matches(&parsed_version)
+			}
+			// Not every mod uses semver, so for anything else the versions need to be the same.
+			_ => requirement.trim() == version.trim(),
+		}
+	}
+}
+
+impl fmt::Display for ModRequirement {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.version {
+			Some(version) => write!(formatter, "{} ({version})", self.id),
+			None => write!(formatter, "{}", self.id),
+		}
+	}
+}
+
+// Mod versions are usually release tags like "v1.2", and can have a redownload id after a slash.
+// Missing numbers are filled in with zeroes, since semver needs all three.
+fn parse_mod_version(version: &str) -> Option<semver::Version> {
+	let version = version
+		.split('/')
+		.next()?
+		.trim()
+		.trim_start_matches(['v', 'V']);
+
+	let mut numbers = version
+		.split('.')
+		.map(str::parse::<u64>)
+		.collect::<std::result::Result<Vec<_>, _>>()
+		.ok()?
+		.into_iter();
+
+	Some(semver::Version::new(
+		numbers.next()?,
+		numbers.next().unwrap_or(0),
+		numbers.next().unwrap_or(0),
+	))
+}
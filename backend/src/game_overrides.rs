@@ -0,0 +1,181 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	game_engines::{
+		game_engine::{
+			GameEngine,
+			GameEngineBrand,
+			GameEngineVersion,
+		},
+		unity::{
+			self,
+			UnityScriptingBackend,
+		},
+	},
+	game_executable::{
+		Architecture,
+		GameExecutable,
+		OperatingSystem,
+	},
+	installed_game::InstalledGame,
+	paths::{
+		app_data_path,
+		legacy_hash_path,
+	},
+	Result,
+};
+
+// User provided values that replace whatever we detected (or failed to detect) for a game.
+#[derive(
+	serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameOverrides {
+	pub name: Option<String>,
+	pub thumbnail_url: Option<String>,
+	pub engine_brand: Option<GameEngineBrand>,
+	pub engine_version: Option<String>,
+	pub scripting_backend: Option<UnityScriptingBackend>,
+	pub architecture: Option<Architecture>,
+	pub operating_system: Option<OperatingSystem>,
+}
+
+// Keys are the installed game ids.
+pub type Map = HashMap<String, GameOverrides>;
+
+impl GameOverrides {
+	pub fn is_empty(&self) -> bool {
+		*self == Self::default()
+	}
+
+	pub fn apply_to_executable(&self, executable: &mut GameExecutable) {
+		if let Some(architecture) = self.architecture {
+			executable.architecture = Some(architecture);
+		}
+
+		if let Some(operating_system) = self.operating_system {
+			executable.operating_system = Some(operating_system);
+		}
+
+		if let Some(scripting_backend) = self.scripting_backend {
+			executable.scripting_backend = Some(scripting_backend);
+		}
+
+		let current_brand = executable.engine.as_ref().map(|engine| engine.brand);
+		if let Some(brand) = self.engine_brand.or(current_brand) {
+			let version = self.engine_version.as_ref().map_or_else(
+				|| {
+					executable
+						.engine
+						.as_ref()
+						.and_then(|engine| engine.version.clone())
+				},
+				|version| Some(parse_engine_version(version)),
+			);
+
+			executable.engine = Some(GameEngine { brand, version });
+		}
+	}
+}
+
+// Accepts Unity versions like "2019.4.1f1", and anything else that looks like "major.minor.patch".
+fn parse_engine_version(version: &str) -> GameEngineVersion {
+	unity::parse_version(version).unwrap_or_else(|| {
+		let mut numbers = version
+			.trim()
+			.split('.')
+			.map(|number| number.parse::<u32>().unwrap_or(0));
+
+		GameEngineVersion {
+			major: numbers.next().unwrap_or(0),
+			minor: numbers.next().unwrap_or(0),
+			patch: numbers.next().unwrap_or(0),
+			suffix: None,
+			display: version.trim().to_string(),
+		}
+	})
+}
+
+fn overrides_path() -> Result<PathBuf> {
+	Ok(app_data_path()?.join("game-overrides.json"))
+}
+
+fn read_overrides(overrides_path: &Path) -> Result<Map> {
+	if !overrides_path.is_file() {
+		return Ok(Map::default());
+	}
+
+	let json = fs::read_to_string(overrides_path)?;
+	Ok(serde_json::from_str::<Map>(&json)?)
+}
+
+pub fn get_all() -> Map {
+	match overrides_path().and_then(|path| read_overrides(&path)) {
+		Ok(overrides) => overrides,
+		Err(err) => {
+			error!("Failed to read game overrides: {err}");
+			Map::default()
+		}
+	}
+}
+
+// Overrides saved with legacy game ids get moved to the new ids.
+pub fn migrate_legacy_ids(overrides: &mut Map, installed_games: &[InstalledGame]) {
+	let id_changes: Vec<_> = installed_games
+		.iter()
+		.map(|game| (legacy_hash_path(&game.executable.path), game.id.clone()))
+		.collect();
+
+	migrate_ids(overrides, &id_changes);
+}
+
+// Each id change is a (previous id, new id) pair.
+pub fn migrate_ids(overrides: &mut Map, id_changes: &[(String, String)]) {
+	let mut changed = false;
+
+	for (previous_id, new_id) in id_changes {
+		if overrides.contains_key(new_id) {
+			continue;
+		}
+
+		if let Some(game_overrides) = overrides.remove(previous_id) {
+			overrides.insert(new_id.clone(), game_overrides);
+			changed = true;
+		}
+	}
+
+	if !changed {
+		return;
+	}
+
+	if let Err(err) = overrides_path().and_then(|path| {
+		fs::write(path, serde_json::to_string_pretty(overrides)?)?;
+		Ok(())
+	}) {
+		error!("Failed to save migrated game overrides: {err}");
+	}
+}
+
+pub fn set(game_id: &str, game_overrides: &GameOverrides) -> Result {
+	let path = overrides_path()?;
+
+	let mut overrides = read_overrides(&path)?;
+	if game_overrides.is_empty() {
+		overrides.remove(game_id);
+	} else {
+		overrides.insert(game_id.to_string(), game_overrides.clone());
+	}
+
+	fs::write(path, serde_json::to_string_pretty(&overrides)?)?;
+
+	Ok(())
+}
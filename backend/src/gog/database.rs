@@ -0,0 +1,191 @@
+use std::path::{
+	Path,
+	PathBuf,
+};
+
+use log::error;
+use rusqlite::{
+	Connection,
+	OpenFlags,
+};
+use serde::Deserialize;
+
+use crate::{
+	serializable_struct,
+	Result,
+};
+
+#[derive(Clone)]
+pub struct GogDbEntry {
+	pub id: String,
+	pub title: String,
+	pub image_url: Option<String>,
+	pub release_date: Option<i32>,
+	pub executable_path: Option<PathBuf>,
+}
+
+serializable_struct!(GogDbEntryTitle { title: Option<String> });
+serializable_struct!(GogDbEntryImages { square_icon: Option<String> });
+serializable_struct!(GogDbEntryMeta { release_date: Option<i32> });
+
+fn get_path(galaxy_path: &Path) -> PathBuf {
+	galaxy_path.join("storage").join("galaxy-2.0.db")
+}
+
+// Reads the database from the GOG Galaxy folder (usually C:/ProgramData/GOG.com/Galaxy).
+pub fn read(galaxy_path: &Path) -> Result<Vec<GogDbEntry>> {
+	parse(&Connection::open_with_flags(
+		get_path(galaxy_path),
+		OpenFlags::SQLITE_OPEN_READ_ONLY,
+	)?)
+}
+
+pub fn parse(connection: &Connection) -> Result<Vec<GogDbEntry>> {
+	let mut statement = connection.prepare(
+		r"SELECT 
+	Builds.productId AS id,
+	MAX(CASE WHEN GamePieceTypes.type = 'originalTitle' THEN GamePieces.value END) AS title,
+	MAX(CASE WHEN GamePieceTypes.type = 'originalImages' THEN GamePieces.value END) AS images,
+	MAX(CASE WHEN GamePieceTypes.type = 'originalMeta' THEN GamePieces.value END) AS meta,
+	MAX(PlayTaskLaunchParameters.executablePath) AS executablePath
+FROM 
+	Builds
+JOIN 
+	GamePieces ON GamePieces.releaseKey = 'gog_' || Builds.productId
+LEFT JOIN 
+	PlayTasks ON GamePieces.releaseKey = PlayTasks.gameReleaseKey
+LEFT JOIN 
+	PlayTaskLaunchParameters ON PlayTasks.id = PlayTaskLaunchParameters.playTaskId
+JOIN
+	GamePieceTypes ON GamePieces.gamePieceTypeId = GamePieceTypes.id
+GROUP BY 
+	Builds.productId;",
+	)?;
+
+	let rows: Vec<GogDbEntry> = statement
+		.query_map([], |row| {
+			let id: i32 = row.get("id")?;
+			let executable_path: Option<String> = try_get_string(row, "executablePath");
+			let title = try_get_json::<GogDbEntryTitle>(row, "title").and_then(|title| title.title);
+			let release_date =
+				try_get_json::<GogDbEntryMeta>(row, "meta").and_then(|meta| meta.release_date);
+			let image_url = try_get_json::<GogDbEntryImages>(row, "images")
+				.and_then(|images| images.square_icon);
+
+			Ok(GogDbEntry {
+				id: id.to_string(),
+				title: title.unwrap_or_else(|| id.to_string()),
+				image_url,
+				release_date,
+				executable_path: executable_path.map(PathBuf::from),
+			})
+		})?
+		.filter_map(|row_result| match row_result {
+			Ok(row) => Some(row),
+			Err(err) => {
+				error!("Failed to read GOG database row: {err}");
+				None
+			}
+		})
+		.collect();
+
+	Ok(rows)
+}
+
+fn try_parse_json<TData>(json: &str) -> Option<TData>
+where
+	TData: for<'a> Deserialize<'a>,
+{
+	match serde_json::from_str::<TData>(json) {
+		Ok(data) => Some(data),
+		Err(err) => {
+			error!("Failed to parse GOG database json `{json}`. Error: {err}");
+			None
+		}
+	}
+}
+
+fn try_get_string(row: &rusqlite::Row, id: &str) -> Option<String> {
+	match row.get::<&str, Option<String>>(id) {
+		Ok(value) => value,
+		Err(err) => {
+			error!("Failed to read GOG database value `{id}`. Error: {err}");
+			None
+		}
+	}
+}
+
+fn try_get_json<TData>(row: &rusqlite::Row, id: &str) -> Option<TData>
+where
+	TData: for<'a> Deserialize<'a>,
+{
+	let json = try_get_string(row, id)?;
+	try_parse_json(&json)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Just the tables and columns the query needs, with the same names Galaxy uses.
+	fn create_database() -> Result<Connection> {
+		let connection = Connection::open_in_memory()?;
+		connection.execute_batch(
+			r#"
+			CREATE TABLE Builds (productId INTEGER);
+			CREATE TABLE GamePieceTypes (id INTEGER, type TEXT);
+			CREATE TABLE GamePieces (releaseKey TEXT, gamePieceTypeId INTEGER, value TEXT);
+			CREATE TABLE PlayTasks (id INTEGER, gameReleaseKey TEXT);
+			CREATE TABLE PlayTaskLaunchParameters (playTaskId INTEGER, executablePath TEXT);
+
+			INSERT INTO GamePieceTypes VALUES
+				(1, 'originalTitle'),
+				(2, 'originalImages'),
+				(3, 'originalMeta');
+
+			INSERT INTO Builds VALUES (1207658924), (1207658925);
+
+			INSERT INTO GamePieces VALUES
+				('gog_1207658924', 1, '{"title":"Some Game"}'),
+				('gog_1207658924', 2, '{"squareIcon":"https://example.com/icon.png"}'),
+				('gog_1207658924', 3, '{"releaseDate":1621526400}'),
+				('gog_1207658925', 3, 'not json');
+
+			INSERT INTO PlayTasks VALUES (1, 'gog_1207658924');
+			INSERT INTO PlayTaskLaunchParameters VALUES (1, 'C:/Games/SomeGame/SomeGame.exe');
+			"#,
+		)?;
+
+		Ok(connection)
+	}
+
+	#[test]
+	fn parses_database_rows() -> Result {
+		let mut entries = parse(&create_database()?)?;
+		entries.sort_by(|a, b| a.id.cmp(&b.id));
+
+		assert_eq!(entries.len(), 2);
+
+		let game = &entries[0];
+		assert_eq!(game.id, "1207658924");
+		assert_eq!(game.title, "Some Game");
+		assert_eq!(
+			game.image_url.as_deref(),
+			Some("https://example.com/icon.png")
+		);
+		assert_eq!(game.release_date, Some(1_621_526_400));
+		assert_eq!(
+			game.executable_path,
+			Some(PathBuf::from("C:/Games/SomeGame/SomeGame.exe"))
+		);
+
+		// Missing or broken json falls back to the id as the title.
+		let broken_game = &entries[1];
+		assert_eq!(broken_game.title, "1207658925");
+		assert_eq!(broken_game.image_url, None);
+		assert_eq!(broken_game.release_date, None);
+		assert_eq!(broken_game.executable_path, None);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,451 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::{
+	error,
+	info,
+};
+
+use crate::{
+	files,
+	installed_game::InstalledGame,
+	paths,
+	serializable_struct,
+	Result,
+};
+
+serializable_struct!(JournalEntry {
+	// Saved relative to the game folders, see GameFolders.
+	pub path: PathBuf,
+	// Where the original file was backed up to.
+	// If None, the file didn't exist before we wrote it.
+	pub backup_path: Option<PathBuf>,
+});
+
+// Keeps track of how every file we ever wrote for a game was before we touched it,
+// so that the game can be restored to how it was.
+serializable_struct!(InstallJournal {
+	pub entries: Vec<JournalEntry>,
+});
+
+// Tokens that saved paths start with, instead of the folder they're in.
+const GAME_FOLDER_TOKEN: &str = "{{GAME_FOLDER}}";
+const MOD_DATA_FOLDER_TOKEN: &str = "{{MOD_DATA_FOLDER}}";
+
+// Where a game and its mod data are right now.
+// Paths get saved relative to these, so they keep working after the game moves (see game_locations).
+pub struct GameFolders {
+	game_folder: PathBuf,
+	mod_data_folder: PathBuf,
+}
+
+impl GameFolders {
+	pub fn get(game: &InstalledGame) -> Result<Self> {
+		Ok(Self {
+			game_folder: paths::path_parent(&game.executable.path)?.to_path_buf(),
+			mod_data_folder: game.get_installed_mods_folder()?,
+		})
+	}
+
+	// The mod data folder goes first, in case it's ever inside the game folder.
+	fn get_folder_tokens(&self) -> [(&'static str, &Path); 2] {
+		[
+			(MOD_DATA_FOLDER_TOKEN, &self.mod_data_folder),
+			(GAME_FOLDER_TOKEN, &self.game_folder),
+		]
+	}
+
+	pub fn to_saved_path(&self, path: &Path) -> PathBuf {
+		self.get_folder_tokens()
+			.iter()
+			.find_map(|(token, folder)| {
+				Some(Path::new(token).join(path.strip_prefix(folder).ok()?))
+			})
+			.unwrap_or_else(|| path.to_path_buf())
+	}
+
+	// Paths saved before we had tokens are absolute, so they're used as they are.
+	pub fn resolve_saved_path(&self, saved_path: &Path) -> PathBuf {
+		self.get_folder_tokens()
+			.iter()
+			.find_map(|(token, folder)| Some(folder.join(saved_path.strip_prefix(token).ok()?)))
+			.unwrap_or_else(|| saved_path.to_path_buf())
+	}
+
+	fn map_entry(entry: &JournalEntry, map_path: impl Fn(&Path) -> PathBuf) -> JournalEntry {
+		JournalEntry {
+			path: map_path(&entry.path),
+			backup_path: entry.backup_path.as_deref().map(&map_path),
+		}
+	}
+
+	fn get_journal_path(&self) -> PathBuf {
+		self.mod_data_folder.join(JOURNAL_FILE_NAME)
+	}
+
+	fn read_journal(&self) -> Result<InstallJournal> {
+		Ok(InstallJournal {
+			entries: read_saved_journal(&self.get_journal_path())?
+				.entries
+				.iter()
+				.map(|entry| Self::map_entry(entry, |path| self.resolve_saved_path(path)))
+				.collect(),
+		})
+	}
+
+	fn write_journal(&self, journal: &InstallJournal) -> Result {
+		let saved_journal = InstallJournal {
+			entries: journal
+				.entries
+				.iter()
+				.map(|entry| Self::map_entry(entry, |path| self.to_saved_path(path)))
+				.collect(),
+		};
+
+		fs::write(
+			self.get_journal_path(),
+			serde_json::to_string_pretty(&saved_journal)?,
+		)?;
+
+		Ok(())
+	}
+}
+
+pub struct InstallTransaction<'a> {
+	folders: &'a GameFolders,
+	backups_path: PathBuf,
+	journal: InstallJournal,
+	// How each file was before this transaction, for rolling back.
+	rollback_entries: Vec<JournalEntry>,
+}
+
+const JOURNAL_FILE_NAME: &str = "journal.json";
+
+fn read_saved_journal(journal_path: &Path) -> Result<InstallJournal> {
+	if !journal_path.is_file() {
+		return Ok(InstallJournal {
+			entries: Vec::default(),
+		});
+	}
+
+	Ok(serde_json::from_str(&fs::read_to_string(journal_path)?)?)
+}
+
+// True if the game still has files that we'd need to put back, using this folder's journal.
+pub fn has_entries(installed_mods_folder: &Path) -> Result<bool> {
+	Ok(
+		!read_saved_journal(&installed_mods_folder.join(JOURNAL_FILE_NAME))?
+			.entries
+			.is_empty(),
+	)
+}
+
+fn restore_entry(entry: &JournalEntry) -> Result {
+	if let Some(backup_path) = &entry.backup_path {
+		fs::create_dir_all(paths::path_parent(&entry.path)?)?;
+		fs::copy(backup_path, &entry.path)?;
+	} else if entry.path.is_file() {
+		fs::remove_file(&entry.path)?;
+	}
+
+	Ok(())
+}
+
+impl<'a> InstallTransaction<'a> {
+	fn begin(folders: &'a GameFolders) -> Result<Self> {
+		Ok(Self {
+			journal: folders.read_journal()?,
+			backups_path: folders.mod_data_folder.join("backups"),
+			folders,
+			rollback_entries: Vec::default(),
+		})
+	}
+
+	// Needs to be called before writing to any file.
+	pub fn prepare_write(&mut self, path: &Path) -> Result {
+		if self.rollback_entries.iter().any(|entry| entry.path == path) {
+			return Ok(());
+		}
+
+		let backup_path = if path.is_file() {
+			fs::create_dir_all(&self.backups_path)?;
+			let backup_path = self
+				.backups_path
+				.join(uuid::Uuid::new_v4().hyphenated().to_string());
+			fs::copy(path, &backup_path)?;
+			Some(backup_path)
+		} else {
+			None
+		};
+
+		let entry = JournalEntry {
+			path: path.to_path_buf(),
+			backup_path,
+		};
+
+		// If we already wrote this file before, the journal already knows how the original was.
+		if !self
+			.journal
+			.entries
+			.iter()
+			.any(|journal_entry| journal_entry.path == path)
+		{
+			self.journal.entries.push(entry.clone());
+		}
+
+		self.rollback_entries.push(entry);
+
+		Ok(())
+	}
+
+	pub fn write(&mut self, path: &Path, contents: impl AsRef<[u8]>) -> Result {
+		self.prepare_write(path)?;
+		fs::write(path, contents)?;
+
+		Ok(())
+	}
+
+	pub fn copy(&mut self, from: &Path, to: &Path) -> Result {
+		self.prepare_write(to)?;
+		fs::copy(from, to)?;
+
+		Ok(())
+	}
+
+	pub fn copy_dir_all(&mut self, src: &Path, dst: &Path) -> Result<Vec<PathBuf>> {
+		files::copy_dir_all_with(src, dst, &mut |path| self.prepare_write(path))
+	}
+
+	fn is_backup_in_journal(&self, backup_path: &Path) -> bool {
+		self.journal
+			.entries
+			.iter()
+			.any(|entry| entry.backup_path.as_deref() == Some(backup_path))
+	}
+
+	// Backups that were only needed for rolling back this transaction.
+	fn remove_unused_backups(&self, keep_journal_backups: bool) {
+		for backup_path in self
+			.rollback_entries
+			.iter()
+			.filter_map(|entry| entry.backup_path.as_ref())
+		{
+			if keep_journal_backups && self.is_backup_in_journal(backup_path) {
+				continue;
+			}
+
+			if let Err(err) = fs::remove_file(backup_path) {
+				error!("Failed to remove backup `{}`: {err}", backup_path.display());
+			}
+		}
+	}
+
+	fn commit(self) -> Result {
+		self.folders.write_journal(&self.journal)?;
+
+		self.remove_unused_backups(true);
+
+		Ok(())
+	}
+
+	// The journal file is only written on commit, so it doesn't need to be reverted here.
+	fn rollback(self) -> Result {
+		for entry in self.rollback_entries.iter().rev() {
+			restore_entry(entry)?;
+		}
+
+		self.remove_unused_backups(false);
+
+		Ok(())
+	}
+}
+
+// Runs the install steps, and undoes every file change they made if any of them fails.
+pub fn run<T, F>(game: &InstalledGame, install: F) -> Result<T>
+where
+	F: FnOnce(&mut InstallTransaction) -> Result<T>,
+{
+	run_in(&GameFolders::get(game)?, &game.name, install)
+}
+
+fn run_in<T, F>(folders: &GameFolders, game_name: &str, install: F) -> Result<T>
+where
+	F: FnOnce(&mut InstallTransaction) -> Result<T>,
+{
+	let mut transaction = InstallTransaction::begin(folders)?;
+
+	match install(&mut transaction) {
+		Ok(result) => {
+			transaction.commit()?;
+			Ok(result)
+		}
+		Err(err) => {
+			error!("Install failed for game {game_name}, rolling back. Error: {err}");
+			if let Err(rollback_err) = transaction.rollback() {
+				error!("Failed to roll back install for game {game_name}: {rollback_err}");
+			}
+			Err(err)
+		}
+	}
+}
+
+// Restores only the given files, and forgets about them.
+// Files that aren't in the journal were created by us before we had journals, so they just get deleted.
+pub fn restore_files(game: &InstalledGame, files: &[PathBuf]) -> Result {
+	restore_files_in(&GameFolders::get(game)?, files)
+}
+
+fn restore_files_in(folders: &GameFolders, files: &[PathBuf]) -> Result {
+	let mut journal = folders.read_journal()?;
+
+	for file in files {
+		if let Some(index) = journal.entries.iter().position(|entry| &entry.path == file) {
+			let entry = journal.entries.remove(index);
+			restore_entry(&entry)?;
+
+			if let Some(backup_path) = entry.backup_path {
+				fs::remove_file(backup_path)?;
+			}
+		} else if file.is_file() {
+			fs::remove_file(file)?;
+		}
+	}
+
+	folders.write_journal(&journal)
+}
+
+// Puts back every file we overwrote, deletes every file we created,
+// and then gets rid of all the mod data for this game.
+pub fn restore(game: &InstalledGame) -> Result {
+	let folders = GameFolders::get(game)?;
+	let journal = folders.read_journal()?;
+
+	for entry in journal.entries.iter().rev() {
+		restore_entry(entry)?;
+	}
+
+	fs::remove_dir_all(&folders.mod_data_folder)?;
+
+	info!(
+		"Restored {} files for game {}, and removed `{}`",
+		journal.entries.len(),
+		game.name,
+		folders.mod_data_folder.display()
+	);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		env,
+		fs,
+		path::{
+			Path,
+			PathBuf,
+		},
+	};
+
+	use super::{
+		restore_files_in,
+		run_in,
+		GameFolders,
+	};
+	use crate::Result;
+
+	fn get_test_folder() -> PathBuf {
+		env::temp_dir().join(format!(
+			"rai-pal-test-{}",
+			uuid::Uuid::new_v4().hyphenated()
+		))
+	}
+
+	fn get_folders(root: &Path, game_folder_name: &str, mod_data_folder_name: &str) -> GameFolders {
+		GameFolders {
+			game_folder: root.join(game_folder_name),
+			mod_data_folder: root.join(mod_data_folder_name),
+		}
+	}
+
+	#[test]
+	fn saved_paths_follow_moved_folders() {
+		let root = get_test_folder();
+		let before = get_folders(&root, "game-before", "mod-data-before");
+		let after = get_folders(&root, "game-after", "mod-data-after");
+
+		let game_file = before.game_folder.join("winhttp.dll");
+		let mod_data_file = before.mod_data_folder.join("BepInEx").join("core.dll");
+		let outside_file = root.join("somewhere-else.dll");
+
+		assert_eq!(
+			after.resolve_saved_path(&before.to_saved_path(&game_file)),
+			after.game_folder.join("winhttp.dll")
+		);
+		assert_eq!(
+			after.resolve_saved_path(&before.to_saved_path(&mod_data_file)),
+			after.mod_data_folder.join("BepInEx").join("core.dll")
+		);
+		assert_eq!(
+			after.resolve_saved_path(&before.to_saved_path(&outside_file)),
+			outside_file
+		);
+	}
+
+	#[test]
+	fn restores_game_files_after_the_game_moves() -> Result {
+		let root = get_test_folder();
+		let before = get_folders(&root, "game-before", "mod-data-before");
+		let after = get_folders(&root, "game-after", "mod-data-after");
+
+		fs::create_dir_all(&before.game_folder)?;
+		fs::create_dir_all(&before.mod_data_folder)?;
+
+		let original_file = before.game_folder.join("winhttp.dll");
+		let created_file = before.game_folder.join("doorstop_config.ini");
+		fs::write(&original_file, "original")?;
+
+		// Install, keeping the list of installed files the way the mod loaders do.
+		let saved_installed_files: Vec<_> = run_in(&before, "test", |transaction| {
+			transaction.write(&original_file, "modded")?;
+			transaction.write(&created_file, "config")?;
+			Ok(vec![original_file.clone(), created_file.clone()])
+		})?
+		.iter()
+		.map(|file| before.to_saved_path(file))
+		.collect();
+
+		assert_eq!(fs::read_to_string(&original_file)?, "modded");
+
+		// Move the game and its mod data, like relink_moved_games does.
+		fs::rename(&before.game_folder, &after.game_folder)?;
+		fs::rename(&before.mod_data_folder, &after.mod_data_folder)?;
+
+		// Uninstall.
+		let installed_files: Vec<_> = saved_installed_files
+			.iter()
+			.map(|file| after.resolve_saved_path(file))
+			.collect();
+		assert!(installed_files
+			.iter()
+			.all(|file| file.starts_with(&after.game_folder)));
+
+		restore_files_in(&after, &installed_files)?;
+
+		assert_eq!(
+			fs::read_to_string(after.game_folder.join("winhttp.dll"))?,
+			"original"
+		);
+		assert!(!after.game_folder.join("doorstop_config.ini").exists());
+		assert!(after.read_journal()?.entries.is_empty());
+
+		fs::remove_dir_all(root)?;
+
+		Ok(())
+	}
+}
@@ -7,16 +7,23 @@ use std::{
 	},
 };
 
-use log::error;
+use log::{
+	error,
+	info,
+};
 
 use crate::{
 	game_executable::GameExecutable,
+	game_overrides::GameOverrides,
+	install_journal::GameFolders,
+	mod_loaders::doorstop,
 	mod_manifest,
 	owned_game,
 	paths::{
 		self,
 		glob_path,
 		hash_path,
+		legacy_hash_path,
 	},
 	providers::{
 		provider::ProviderId,
@@ -33,20 +40,30 @@ serializable_struct!(InstalledGame {
 	pub provider: ProviderId,
 	pub executable: GameExecutable,
 	pub installed_mod_versions: InstalledModVersions,
+	// When true, the game starts without any mod loader.
+	pub launch_vanilla: bool,
 	pub discriminator: Option<String>,
 	pub thumbnail_url: Option<String>,
 	pub owned_game_id: Option<String>,
 	pub start_command: Option<ProviderCommand>,
+	pub overrides: GameOverrides,
 });
 
 pub type Map = HashMap<String, InstalledGame>;
-type InstalledModVersions = HashMap<String, String>;
+serializable_struct!(InstalledModVersion {
+	pub version: String,
+	pub enabled: bool,
+	// Pinned mods are skipped when checking for updates.
+	pub pinned_version: Option<String>,
+});
+
+type InstalledModVersions = HashMap<String, InstalledModVersion>;
+
+// Games exported by Unity always have one of these extensions.
+pub const VALID_EXTENSIONS: [&str; 3] = ["exe", "x86_64", "x86"];
 
 impl InstalledGame {
 	pub fn new(path: &Path, name: &str, provider_id: ProviderId) -> Option<Self> {
-		// Games exported by Unity always have one of these extensions.
-		const VALID_EXTENSIONS: [&str; 3] = ["exe", "x86_64", "x86"];
-
 		if !path.is_file() {
 			return None;
 		}
@@ -74,13 +91,23 @@ impl InstalledGame {
 			name: name.to_string(),
 			provider: provider_id,
 			installed_mod_versions: HashMap::default(),
+			launch_vanilla: false,
 			executable: GameExecutable::new(path)?,
 			discriminator: None,
 			thumbnail_url: None,
 			start_command: None,
 			owned_game_id: None,
+			overrides: GameOverrides::default(),
 		};
 
+		// Needs to happen before anything else touches the installed mods folder.
+		if let Err(err) = installed_game.migrate_legacy_id() {
+			error!(
+				"Failed to migrate legacy installed mods folder for game {}: {err}",
+				installed_game.executable.path.display()
+			);
+		}
+
 		installed_game.refresh_installed_mods();
 
 		Some(installed_game)
@@ -111,8 +138,25 @@ impl InstalledGame {
 		self
 	}
 
+	// Overrides are applied on top of the detected data,
+	// and are kept so they can be applied again when the executable is refreshed.
+	pub fn set_overrides(&mut self, overrides: &GameOverrides) -> &Self {
+		if let Some(name) = &overrides.name {
+			self.name = name.clone();
+		}
+
+		if let Some(thumbnail_url) = &overrides.thumbnail_url {
+			self.thumbnail_url = Some(thumbnail_url.clone());
+		}
+
+		overrides.apply_to_executable(&mut self.executable);
+		self.overrides = overrides.clone();
+		self
+	}
+
 	pub fn refresh_executable(&mut self) -> Result {
-		if let Some(executable) = GameExecutable::new(&self.executable.path) {
+		if let Some(mut executable) = GameExecutable::new(&self.executable.path) {
+			self.overrides.apply_to_executable(&mut executable);
 			self.executable = executable;
 		} else {
 			return Err(Error::FailedToGetGameFromPath(self.executable.path.clone()));
@@ -123,6 +167,7 @@ impl InstalledGame {
 
 	pub fn refresh_installed_mods(&mut self) {
 		self.installed_mod_versions = self.get_available_mods();
+		self.launch_vanilla = !doorstop::is_enabled(&self.executable.path);
 	}
 
 	pub fn open_game_folder(&self) -> Result {
@@ -145,19 +190,17 @@ impl InstalledGame {
 		Ok(open::that_detached(&self.executable.path)?)
 	}
 
-	pub fn uninstall_mod(&self, mod_id: &str) -> Result {
-		// TODO this should be handled by each mod loader.
-		let installed_mods_folder = self.get_installed_mods_folder()?;
-		let bepinex_folder = installed_mods_folder.join("BepInEx");
-
-		let plugins_folder = bepinex_folder.join("plugins").join(mod_id);
-		if plugins_folder.is_dir() {
-			fs::remove_dir_all(plugins_folder)?;
+	// Removes everything we know about an installed mod: the files it installed, its file list and its manifest.
+	pub fn remove_installed_mod_data(&self, mod_id: &str) -> Result {
+		for installed_file in self.get_installed_files(mod_id)? {
+			if installed_file.is_file() {
+				fs::remove_file(installed_file)?;
+			}
 		}
 
-		let patchers_folder = bepinex_folder.join("patchers").join(mod_id);
-		if patchers_folder.is_dir() {
-			fs::remove_dir_all(patchers_folder)?;
+		let installed_files_path = self.get_installed_files_path(mod_id)?;
+		if installed_files_path.is_file() {
+			fs::remove_file(installed_files_path)?;
 		}
 
 		let manifest_path = self.get_installed_mod_manifest_path(mod_id)?;
@@ -165,6 +208,8 @@ impl InstalledGame {
 			fs::remove_file(manifest_path)?;
 		}
 
+		self.set_mod_enabled_state(mod_id, true)?;
+
 		Ok(())
 	}
 
@@ -181,20 +226,116 @@ impl InstalledGame {
 		}
 	}
 
-	pub fn get_available_mods(&self) -> InstalledModVersions {
+	// Keys are mod ids.
+	pub fn get_installed_manifests(&self) -> HashMap<String, mod_manifest::Manifest> {
 		self.get_manifest_paths()
 			.iter()
 			.filter_map(|manifest_path| {
-				let manifest = mod_manifest::get(manifest_path)?;
-
 				Some((
 					manifest_path.file_stem()?.to_str()?.to_string(),
-					manifest.version,
+					mod_manifest::get(manifest_path)?,
 				))
 			})
 			.collect()
 	}
 
+	pub fn get_available_mods(&self) -> InstalledModVersions {
+		let disabled_mods = self.get_disabled_mods();
+		let pinned_mods = self.get_pinned_mods();
+
+		self.get_installed_manifests()
+			.into_iter()
+			.map(|(mod_id, manifest)| {
+				let enabled = !disabled_mods.contains(&mod_id);
+				let pinned_version = pinned_mods.get(&mod_id).cloned();
+
+				(
+					mod_id,
+					InstalledModVersion {
+						version: manifest.version,
+						enabled,
+						pinned_version,
+					},
+				)
+			})
+			.collect()
+	}
+
+	fn get_disabled_mods_path(&self) -> Result<PathBuf> {
+		Ok(self.get_installed_mods_folder()?.join("disabled-mods.json"))
+	}
+
+	pub fn get_disabled_mods(&self) -> Vec<String> {
+		let disabled_mods = self
+			.get_disabled_mods_path()
+			.and_then(|disabled_mods_path| {
+				if !disabled_mods_path.is_file() {
+					return Ok(Vec::default());
+				}
+
+				Ok(serde_json::from_str(&fs::read_to_string(
+					disabled_mods_path,
+				)?)?)
+			});
+
+		disabled_mods.unwrap_or_else(|err| {
+			error!("Failed to read disabled mods for game {}: {err}", self.id);
+			Vec::default()
+		})
+	}
+
+	pub fn set_mod_enabled_state(&self, mod_id: &str, enabled: bool) -> Result {
+		let mut disabled_mods = self.get_disabled_mods();
+		disabled_mods.retain(|disabled_mod_id| disabled_mod_id != mod_id);
+		if !enabled {
+			disabled_mods.push(mod_id.to_string());
+		}
+
+		fs::write(
+			self.get_disabled_mods_path()?,
+			serde_json::to_string_pretty(&disabled_mods)?,
+		)?;
+
+		Ok(())
+	}
+
+	fn get_pinned_mods_path(&self) -> Result<PathBuf> {
+		Ok(self.get_installed_mods_folder()?.join("pinned-mods.json"))
+	}
+
+	// Keys are mod ids, values are the pinned versions.
+	pub fn get_pinned_mods(&self) -> HashMap<String, String> {
+		let pinned_mods = self.get_pinned_mods_path().and_then(|pinned_mods_path| {
+			if !pinned_mods_path.is_file() {
+				return Ok(HashMap::default());
+			}
+
+			Ok(serde_json::from_str(&fs::read_to_string(
+				pinned_mods_path,
+			)?)?)
+		});
+
+		pinned_mods.unwrap_or_else(|err| {
+			error!("Failed to read pinned mods for game {}: {err}", self.id);
+			HashMap::default()
+		})
+	}
+
+	pub fn set_mod_pinned_version(&self, mod_id: &str, version: Option<&str>) -> Result {
+		let mut pinned_mods = self.get_pinned_mods();
+		match version {
+			Some(version) => pinned_mods.insert(mod_id.to_string(), version.to_string()),
+			None => pinned_mods.remove(mod_id),
+		};
+
+		fs::write(
+			self.get_pinned_mods_path()?,
+			serde_json::to_string_pretty(&pinned_mods)?,
+		)?;
+
+		Ok(())
+	}
+
 	pub fn get_installed_mod_manifest_path(&self, mod_id: &str) -> Result<PathBuf> {
 		Ok(self
 			.get_installed_mods_folder()?
@@ -202,12 +343,109 @@ impl InstalledGame {
 			.join(format!("{mod_id}.json")))
 	}
 
+	// Lists of files written by each mod or mod loader install, keyed by mod or mod loader id.
+	fn get_installed_files_path(&self, id: &str) -> Result<PathBuf> {
+		Ok(self
+			.get_installed_mods_folder()?
+			.join("files")
+			.join(format!("{id}.json")))
+	}
+
+	pub fn get_installed_files(&self, id: &str) -> Result<Vec<PathBuf>> {
+		let installed_files_path = self.get_installed_files_path(id)?;
+		if !installed_files_path.is_file() {
+			return Ok(Vec::default());
+		}
+
+		let folders = GameFolders::get(self)?;
+		let saved_files: Vec<PathBuf> =
+			serde_json::from_str(&fs::read_to_string(installed_files_path)?)?;
+
+		Ok(saved_files
+			.iter()
+			.map(|saved_file| folders.resolve_saved_path(saved_file))
+			.collect())
+	}
+
+	// Saved relative to the game folders, so the list still works after the game moves.
+	pub fn save_installed_files(&self, id: &str, files: &[PathBuf]) -> Result {
+		let folders = GameFolders::get(self)?;
+		let saved_files: Vec<_> = files
+			.iter()
+			.map(|file| folders.to_saved_path(file))
+			.collect();
+
+		let installed_files_path = self.get_installed_files_path(id)?;
+		fs::create_dir_all(paths::path_parent(&installed_files_path)?)?;
+		fs::write(
+			installed_files_path,
+			serde_json::to_string_pretty(&saved_files)?,
+		)?;
+
+		Ok(())
+	}
+
 	pub fn get_installed_mods_folder(&self) -> Result<PathBuf> {
-		let installed_mods_folder = paths::app_data_path()?
-			.join("installed-mods")
-			.join(&self.id);
+		let installed_mods_folder = get_installed_mods_root()?.join(&self.id);
 		fs::create_dir_all(&installed_mods_folder)?;
 
 		Ok(installed_mods_folder)
 	}
+
+	// Game ids used to be made with a hasher that can change between Rust versions.
+	// If we find a folder with the old id, we move it to the new one.
+	fn migrate_legacy_id(&self) -> Result {
+		let legacy_folder =
+			get_installed_mods_root()?.join(legacy_hash_path(&self.executable.path));
+
+		self.move_installed_mods_folder(&legacy_folder)
+	}
+
+	// Moves mod data from a folder that used to belong to this game.
+	// The doorstop config in the game folder points to the mod data folder, so that needs to be updated too.
+	pub fn move_installed_mods_folder(&self, previous_folder: &Path) -> Result {
+		let installed_mods_folder = get_installed_mods_root()?.join(&self.id);
+
+		if !previous_folder.is_dir() || previous_folder == installed_mods_folder {
+			return Ok(());
+		}
+
+		if installed_mods_folder.is_dir() {
+			if fs::read_dir(&installed_mods_folder)?.next().is_some() {
+				return Err(Error::ModDataFolderNotEmpty(
+					previous_folder.to_path_buf(),
+					installed_mods_folder,
+				));
+			}
+
+			fs::remove_dir(&installed_mods_folder)?;
+		}
+
+		fs::rename(previous_folder, &installed_mods_folder)?;
+
+		info!(
+			"Moved installed mods folder from `{}` to `{}`",
+			previous_folder.display(),
+			installed_mods_folder.display()
+		);
+
+		let doorstop_config_path = doorstop::get_config_path(&self.executable.path)?;
+
+		if doorstop_config_path.is_file() {
+			let doorstop_config = fs::read_to_string(&doorstop_config_path)?;
+			fs::write(
+				&doorstop_config_path,
+				doorstop_config.replace(
+					previous_folder.to_string_lossy().as_ref(),
+					installed_mods_folder.to_string_lossy().as_ref(),
+				),
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+pub fn get_installed_mods_root() -> Result<PathBuf> {
+	Ok(paths::app_data_path()?.join("installed-mods"))
 }
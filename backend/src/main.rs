@@ -14,34 +14,63 @@ use app_state::{
 	StateData,
 	StatefulHandle,
 };
+use downloads::DownloadProgress;
 use events::{
 	AppEvent,
 	EventEmitter,
 };
+use game_overrides::GameOverrides;
 use installed_game::InstalledGame;
+use local_mod::{
+	LocalMod,
+	LocalModData,
+};
 use log::error;
 use maps::TryGettable;
-use mod_loaders::mod_loader::{
-	self,
-	ModLoaderActions,
+use mod_loaders::{
+	mod_database::{
+		ModDownload,
+		ModRelease,
+	},
+	mod_database_sources::{
+		self,
+		ModDatabaseSource,
+	},
+	mod_loader::{
+		self,
+		ModLoaderActions,
+	},
+};
+use mod_profiles::{
+	ModProfile,
+	ModProfileChanges,
 };
-use paths::{
-	hash_path,
-	normalize_path,
+use mod_updates::{
+	ModUpdate,
+	ModUpdateFailure,
+	ModUpdateSummary,
 };
+use mod_versions::ModVersion;
+use paths::normalize_path;
 use providers::{
 	manual_provider,
 	provider::{
 		self,
 		ProviderActions,
+		ProviderId,
 	},
 	provider_command::ProviderCommandAction,
+	provider_settings::{
+		self,
+		ProviderSettings,
+	},
+	steam_provider,
 };
+use remote_mod::RemoteMod;
 use result::{
 	Error,
 	Result,
 };
-use steamlocate::SteamDir;
 use tauri::{
 	AppHandle,
 	Manager,
@@ -50,19 +79,32 @@ use tauri_plugin_log::LogTarget;
 
 mod analytics;
 mod app_state;
+mod archives;
 mod debug;
+mod disk_usage;
+mod downloads;
+mod epic;
 mod events;
 mod files;
 mod game_engines;
 mod game_executable;
+mod game_locations;
 mod game_mod;
 mod game_mode;
+mod game_overrides;
+mod gog;
+mod install_journal;
 mod installed_game;
 mod local_mod;
 mod macros;
 mod maps;
+mod mod_dependencies;
+mod mod_import;
 mod mod_loaders;
 mod mod_manifest;
+mod mod_profiles;
+mod mod_updates;
+mod mod_versions;
 mod operating_systems;
 mod owned_game;
 mod paths;
@@ -152,6 +194,27 @@ async fn open_mods_folder() -> Result {
 	Ok(open::that_detached(paths::installed_mods_path()?)?)
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn get_disk_usage(handle: AppHandle) -> Result<disk_usage::DiskUsageReport> {
+	disk_usage::get_report(&handle.app_state().installed_games.get_data()?)
+}
+
+// Only removes mod data for games that aren't installed anymore.
+// The folder ids should come from the orphaned games in `get_disk_usage`, which works as a dry run.
+// Fails if the installed games haven't been loaded yet, so we don't treat every folder as orphaned.
+#[tauri::command]
+#[specta::specta]
+async fn remove_orphaned_mod_data(
+	folder_ids: Vec<String>,
+	handle: AppHandle,
+) -> Result<Vec<disk_usage::DiskUsage>> {
+	disk_usage::remove_orphaned_game_folders(
+		&handle.app_state().installed_games.get_data()?,
+		&folder_ids,
+	)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn open_mod_folder(mod_id: &str, handle: AppHandle) -> Result {
@@ -171,20 +234,203 @@ async fn open_mod_loader_folder(mod_loader_id: &str, handle: AppHandle) -> Resul
 #[tauri::command]
 #[specta::specta]
 async fn download_mod(mod_id: &str, handle: AppHandle) -> Result {
+	let remote_mod = handle.app_state().remote_mods.try_get(mod_id)?;
+
+	let latest_version = remote_mod
+		.data
+		.latest_version
+		.clone()
+		.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.to_string()))?;
+
+	download_remote_mod(&remote_mod, &latest_version, &handle).await
+}
+
+// Downloads any release of a mod hosted on GitHub. Uses the mod's usual asset if none is given.
+// Releases other than the latest one only end up in the versions folder, see install_mod_version.
+#[tauri::command]
+#[specta::specta]
+async fn download_mod_release(
+	mod_id: &str,
+	tag: &str,
+	asset_name: Option<String>,
+	handle: AppHandle,
+) -> Result {
+	let remote_mod = handle.app_state().remote_mods.try_get(mod_id)?;
+
+	let mod_download = remote_mod
+		.data
+		.github
+		.as_ref()
+		.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.to_string()))?
+		.get_release_download(tag, asset_name.as_deref());
+
+	download_remote_mod(&remote_mod, &mod_download, &handle).await
+}
+
+// Newest releases first. Only available for mods hosted on GitHub.
+// Fetched on demand, since it's a request per mod.
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_changelog(mod_id: &str, handle: AppHandle) -> Result<Vec<ModRelease>> {
+	handle
+		.app_state()
+		.remote_mods
+		.try_get(mod_id)?
+		.data
+		.github
+		.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.to_string()))?
+		.get_releases()
+		.await
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_release_assets(mod_id: &str, tag: &str, handle: AppHandle) -> Result<Vec<String>> {
+	handle
+		.app_state()
+		.remote_mods
+		.try_get(mod_id)?
+		.data
+		.github
+		.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.to_string()))?
+		.get_release_assets(tag)
+		.await
+}
+
+async fn download_remote_mod(
+	remote_mod: &RemoteMod,
+	mod_download: &ModDownload,
+	handle: &AppHandle,
+) -> Result {
 	let state = handle.app_state();
-	let remote_mod = state.remote_mods.try_get(mod_id)?;
+	let mod_id = &remote_mod.common.id;
 	let mod_loaders = state.mod_loaders.get_data()?;
 
 	mod_loaders
 		.try_get(&remote_mod.common.loader_id)?
-		.download_mod(&remote_mod)
+		.download_mod(remote_mod, mod_download, |progress| {
+			handle.emit_event(AppEvent::ModDownloadProgress, progress);
+		})
 		.await?;
 
-	refresh_local_mods(&mod_loaders, &handle);
+	refresh_local_mods(&mod_loaders, handle);
+
+	// Versions that any game is using or has pinned are never pruned.
+	let kept_versions: Vec<_> = state
+		.installed_games
+		.get_data()?
+		.values()
+		.filter_map(|game| game.installed_mod_versions.get(mod_id))
+		.flat_map(|installed_mod| {
+			[
+				Some(installed_mod.version.clone()),
+				installed_mod.pinned_version.clone(),
+			]
+		})
+		.flatten()
+		.collect();
+
+	mod_versions::prune(&remote_mod.common.loader_id, mod_id, &kept_versions)?;
 
 	Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_versions(mod_id: &str, handle: AppHandle) -> Result<Vec<ModVersion>> {
+	Ok(mod_versions::get_all(
+		&get_mod_loader_id(mod_id, &handle)?,
+		mod_id,
+	))
+}
+
+// Installs a previously downloaded version of a mod, without touching its dependencies.
+#[tauri::command]
+#[specta::specta]
+async fn install_mod_version(
+	game_id: &str,
+	mod_id: &str,
+	version: &str,
+	handle: AppHandle,
+) -> Result {
+	let state = handle.app_state();
+	let loader_id = get_mod_loader_id(mod_id, &handle)?;
+	let mod_version = mod_versions::get(&loader_id, mod_id, version)?;
+
+	let common = state.local_mods.try_get(mod_id).map_or_else(
+		|_| {
+			state
+				.remote_mods
+				.try_get(mod_id)
+				.map(|remote_mod| remote_mod.common)
+		},
+		|local_mod| Ok(local_mod.common),
+	)?;
+
+	let version_local_mod = LocalMod {
+		data: LocalModData {
+			manifest: mod_manifest::get(&local_mod::get_manifest_path(&mod_version.path)),
+			path: mod_version.path,
+		},
+		common,
+	};
+
+	state
+		.mod_loaders
+		.try_get(&loader_id)?
+		.install_mod(&state.installed_games.try_get(game_id)?, &version_local_mod)
+		.await?;
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
+// Pinned mods are skipped by update checks. Passing no version unpins the mod.
+#[tauri::command]
+#[specta::specta]
+async fn pin_mod_version(
+	game_id: &str,
+	mod_id: &str,
+	version: Option<String>,
+	handle: AppHandle,
+) -> Result {
+	let game = handle.app_state().installed_games.try_get(game_id)?;
+
+	if let Some(version) = &version {
+		let is_installed = game
+			.installed_mod_versions
+			.get(mod_id)
+			.is_some_and(|installed_mod| &installed_mod.version == version);
+
+		if !is_installed {
+			install_mod_version(game_id, mod_id, version, handle.clone()).await?;
+		}
+	}
+
+	game.set_mod_pinned_version(mod_id, version.as_deref())?;
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
+// The path can be a mod archive or a mod folder.
+// Fails if a mod with the same id already exists, unless `overwrite` is set.
+#[tauri::command]
+#[specta::specta]
+async fn import_mod(path: PathBuf, overwrite: bool, handle: AppHandle) -> Result<String> {
+	let mod_loaders = handle.app_state().mod_loaders.get_data()?;
+
+	let mod_id = mod_import::import(&path, overwrite, &mod_loaders)?;
+
+	refresh_local_mods(&mod_loaders, &handle);
+
+	Ok(mod_id)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn cancel_mod_download(mod_id: &str) -> Result {
+	downloads::cancel(mod_id)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn start_game(game_id: &str, handle: AppHandle) -> Result {
@@ -209,11 +455,31 @@ async fn start_game_exe(game_id: &str, handle: AppHandle) -> Result {
 		.start_exe()
 }
 
+// Installs the mod after any dependencies it needs, as long as none of them conflict with what's already installed.
 #[tauri::command]
 #[specta::specta]
 async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 	let state = handle.app_state();
 
+	let install_plan = mod_dependencies::resolve(
+		mod_id,
+		&state.installed_games.try_get(game_id)?,
+		&state.local_mods.get_data().unwrap_or_default(),
+		&state.remote_mods.get_data().unwrap_or_default(),
+	)?;
+
+	for mod_to_install in install_plan {
+		install_single_mod(game_id, &mod_to_install, &handle).await?;
+	}
+
+	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
+
+	Ok(())
+}
+
+async fn install_single_mod(game_id: &str, mod_id: &str, handle: &AppHandle) -> Result {
+	let state = handle.app_state();
+
 	let mut installed_games = state.installed_games.get_data()?;
 	let game = installed_games.try_get_mut(game_id)?;
 	let mod_loaders = state.mod_loaders.get_data()?;
@@ -225,9 +491,9 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 		} else {
 			// Local mod wasn't in app state,
 			// so let's sync app state to local files in case some file was manually changed.
-			let disk_local_mods = refresh_local_mods(&mod_loaders, &handle);
+			let disk_local_mods = refresh_local_mods(&mod_loaders, handle);
 
-			if state_local_mods.contains_key(mod_id) {
+			if disk_local_mods.contains_key(mod_id) {
 				disk_local_mods
 			} else {
 				let remote_mod = state.remote_mods.try_get(mod_id)?;
@@ -236,16 +502,14 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 				if remote_mod.data.latest_version.is_some() {
 					// If local mod still can't be found on disk,
 					// we try to download it from the database.
-					mod_loader
-						.download_mod(&state.remote_mods.try_get(mod_id)?)
-						.await?;
+					download_mod(mod_id, handle.clone()).await?;
 				} else {
 					// If downloading from the database isn't possible,
 					// we just open the mod loader folder so the user can install it themselves.
 					mod_loader.open_folder()?;
 				}
 
-				refresh_local_mods(&mod_loaders, &handle)
+				refresh_local_mods(&mod_loaders, handle)
 			}
 		}
 	};
@@ -256,11 +520,7 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 
 	mod_loader.install_mod(game, local_mod).await?;
 
-	refresh_game_mods_and_exe(&game.id, &handle)?;
-
-	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
-
-	Ok(())
+	refresh_game_mods_and_exe(&game.id, handle)
 }
 
 fn refresh_game_mods_and_exe(game_id: &str, handle: &AppHandle) -> Result {
@@ -289,19 +549,277 @@ async fn refresh_game(game_id: &str, handle: AppHandle) -> Result {
 	refresh_game_mods_and_exe(game_id, &handle)
 }
 
+// The local mod files might have been deleted, but we can still find the loader from the remote mod.
+fn get_mod_loader_id(mod_id: &str, handle: &AppHandle) -> Result<String> {
+	let state = handle.app_state();
+
+	state.local_mods.try_get(mod_id).map_or_else(
+		|_| {
+			state
+				.remote_mods
+				.try_get(mod_id)
+				.map(|remote_mod| remote_mod.common.loader_id)
+		},
+		|local_mod| Ok(local_mod.common.loader_id),
+	)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn uninstall_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 	let state = handle.app_state();
-	let mut installed_games = state.installed_games.get_data()?;
-	let game = installed_games.try_get_mut(game_id)?;
-	game.uninstall_mod(mod_id)?;
+	let game = state.installed_games.try_get(game_id)?;
 
-	refresh_game_mods_and_exe(&game.id, &handle)?;
+	if let Ok(mod_loader_id) = get_mod_loader_id(mod_id, &handle) {
+		state
+			.mod_loaders
+			.try_get(&mod_loader_id)?
+			.uninstall_mod(&game, mod_id)?;
+	} else {
+		// The mod can still be installed in the game after being deleted locally and removed from the database.
+		// We don't know which loader installed it then, so each loader removes whatever it finds.
+		for mod_loader in state.mod_loaders.get_data()?.values() {
+			mod_loader.uninstall_mod(&game, mod_id)?;
+		}
+	}
+
+	refresh_game_mods_and_exe(game_id, &handle)?;
 
 	Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn set_mod_enabled(game_id: &str, mod_id: &str, enabled: bool, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let game = state.installed_games.try_get(game_id)?;
+
+	state
+		.mod_loaders
+		.try_get(&get_mod_loader_id(mod_id, &handle)?)?
+		.set_mod_enabled(&game, mod_id, enabled)?;
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
+// Lets the game start without any mods, without having to uninstall them.
+#[tauri::command]
+#[specta::specta]
+async fn set_launch_vanilla(game_id: &str, launch_vanilla: bool, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let game = state.installed_games.try_get(game_id)?;
+
+	for mod_loader in state.mod_loaders.get_data()?.values() {
+		mod_loader.set_enabled(&game, !launch_vanilla)?;
+	}
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_profiles(game_id: &str, handle: AppHandle) -> Result<Vec<ModProfile>> {
+	mod_profiles::get_all(&handle.app_state().installed_games.try_get(game_id)?)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn create_mod_profile(game_id: &str, name: &str, handle: AppHandle) -> Result<ModProfile> {
+	mod_profiles::create(&handle.app_state().installed_games.try_get(game_id)?, name)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn rename_mod_profile(
+	game_id: &str,
+	name: &str,
+	new_name: &str,
+	handle: AppHandle,
+) -> Result {
+	mod_profiles::rename(
+		&handle.app_state().installed_games.try_get(game_id)?,
+		name,
+		new_name,
+	)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn delete_mod_profile(game_id: &str, name: &str, handle: AppHandle) -> Result {
+	mod_profiles::delete(&handle.app_state().installed_games.try_get(game_id)?, name)
+}
+
+// Installs or enables every mod in the profile, and disables every other mod.
+// Mods outside the profile are only disabled, so switching back to another profile is quick.
+#[tauri::command]
+#[specta::specta]
+async fn apply_mod_profile(
+	game_id: &str,
+	name: &str,
+	handle: AppHandle,
+) -> Result<ModProfileChanges> {
+	let state = handle.app_state();
+	let game = state.installed_games.try_get(game_id)?;
+	let profile = mod_profiles::get(&game, name)?;
+
+	let mut changes = ModProfileChanges {
+		installed: Vec::default(),
+		enabled: Vec::default(),
+		disabled: Vec::default(),
+		unavailable: Vec::default(),
+		version_mismatches: Vec::default(),
+	};
+
+	for (mod_id, version) in &profile.mods {
+		match game.installed_mod_versions.get(mod_id) {
+			Some(installed_mod) if &installed_mod.version == version => {
+				if !installed_mod.enabled {
+					set_mod_enabled(game_id, mod_id, true, handle.clone()).await?;
+					changes.enabled.push(mod_id.clone());
+				}
+			}
+			_ => {
+				// Prefer the exact version from the profile, if we still have it downloaded.
+				let has_profile_version = !version.is_empty()
+					&& get_mod_loader_id(mod_id, &handle).map_or(false, |loader_id| {
+						mod_versions::get(&loader_id, mod_id, version).is_ok()
+					});
+
+				if has_profile_version {
+					install_mod_version(game_id, mod_id, version, handle.clone()).await?;
+					changes.installed.push(mod_id.clone());
+					continue;
+				}
+
+				let is_available = state.local_mods.try_get(mod_id).is_ok()
+					|| state
+						.remote_mods
+						.try_get(mod_id)
+						.map_or(false, |remote_mod| remote_mod.data.latest_version.is_some());
+
+				if is_available {
+					install_mod(game_id, mod_id, handle.clone()).await?;
+					changes.installed.push(mod_id.clone());
+				} else {
+					changes.unavailable.push(mod_id.clone());
+				}
+			}
+		}
+	}
+
+	for (mod_id, installed_mod) in &game.installed_mod_versions {
+		if installed_mod.enabled && !profile.mods.contains_key(mod_id) {
+			set_mod_enabled(game_id, mod_id, false, handle.clone()).await?;
+			changes.disabled.push(mod_id.clone());
+		}
+	}
+
+	// Needs to happen after installing, since installing mods always turns the mod loader back on.
+	set_launch_vanilla(game_id, profile.launch_vanilla, handle.clone()).await?;
+
+	// When the profile version isn't downloaded anymore we fall back to the latest one, which might not match.
+	let updated_game = state.installed_games.try_get(game_id)?;
+	changes.version_mismatches = changes
+		.installed
+		.iter()
+		.filter(|mod_id| {
+			updated_game
+				.installed_mod_versions
+				.get(*mod_id)
+				.map(|installed_mod| &installed_mod.version)
+				!= profile.mods.get(*mod_id)
+		})
+		.cloned()
+		.collect();
+
+	Ok(changes)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_updates(handle: AppHandle) -> Result<Vec<ModUpdate>> {
+	let state = handle.app_state();
+
+	Ok(mod_updates::get_all(
+		&state.installed_games.get_data()?,
+		&state.local_mods.get_data().unwrap_or_default(),
+		&state.remote_mods.get_data().unwrap_or_default(),
+	))
+}
+
+// Updates a single mod, or every mod if mod_id is None.
+// Same for games: only the given game, or every game if game_id is None.
+#[tauri::command]
+#[specta::specta]
+async fn update_installed_mods(
+	game_id: Option<String>,
+	mod_id: Option<String>,
+	handle: AppHandle,
+) -> Result<ModUpdateSummary> {
+	let updates: Vec<_> = get_mod_updates(handle.clone())
+		.await?
+		.into_iter()
+		.filter(|update| {
+			game_id
+				.as_ref()
+				.map_or(true, |game_id| &update.game_id == game_id)
+				&& mod_id
+					.as_ref()
+					.map_or(true, |mod_id| &update.mod_id == mod_id)
+		})
+		.collect();
+
+	let mut summary = ModUpdateSummary {
+		updated: Vec::default(),
+		failed: Vec::default(),
+	};
+
+	// The same mod can be outdated in many games, but it only needs to be downloaded once.
+	let mut downloaded_mod_ids: Vec<String> = Vec::default();
+
+	for update in updates {
+		let result = async {
+			if update.needs_download && !downloaded_mod_ids.contains(&update.mod_id) {
+				download_mod(&update.mod_id, handle.clone()).await?;
+				downloaded_mod_ids.push(update.mod_id.clone());
+			}
+
+			install_mod(&update.game_id, &update.mod_id, handle.clone()).await
+		}
+		.await;
+
+		match result {
+			Ok(()) => summary.updated.push(update),
+			Err(err) => {
+				error!(
+					"Failed to update mod {} in game {}: {err}",
+					update.mod_id, update.game_id
+				);
+				summary.failed.push(ModUpdateFailure {
+					game_id: update.game_id,
+					mod_id: update.mod_id,
+					error: err.to_string(),
+				});
+			}
+		}
+	}
+
+	handle.emit_event(AppEvent::ModUpdatesFinished, summary.clone());
+
+	Ok(summary)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn uninstall_mod_loader(game_id: &str, mod_loader_id: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let game = state.installed_games.try_get(game_id)?;
+
+	state.mod_loaders.try_get(mod_loader_id)?.uninstall(&game)?;
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
 fn refresh_local_mods(mod_loaders: &mod_loader::Map, handle: &AppHandle) -> local_mod::Map {
 	let local_mods: HashMap<_, _> = mod_loaders
 		.values()
@@ -346,7 +864,7 @@ async fn refresh_remote_mods(mod_loaders: &mod_loader::Map, handle: &AppHandle)
 }
 
 async fn update_installed_games(handle: AppHandle, provider_map: provider::Map) {
-	let installed_games: HashMap<_, _> = provider_map
+	let mut installed_games: Vec<_> = provider_map
 		.iter()
 		.flat_map(|(provider_id, provider)| {
 			let installed_games = provider.get_installed_games();
@@ -359,7 +877,23 @@ async fn update_installed_games(handle: AppHandle, provider_map: provider::Map)
 				}
 			}
 		})
-		.map(|game| (game.id.clone(), game))
+		.collect();
+
+	let id_changes = game_locations::relink_moved_games(&mut installed_games);
+
+	let mut overrides = game_overrides::get_all();
+	game_overrides::migrate_legacy_ids(&mut overrides, &installed_games);
+	game_overrides::migrate_ids(&mut overrides, &id_changes);
+
+	let installed_games: HashMap<_, _> = installed_games
+		.into_iter()
+		.map(|mut game| {
+			if let Some(game_overrides) = overrides.get(&game.id) {
+				game.set_overrides(game_overrides);
+			}
+
+			(game.id.clone(), game)
+		})
 		.collect();
 
 	update_state(
@@ -453,18 +987,25 @@ async fn update_data(handle: AppHandle) -> Result {
 	Ok(())
 }
 
+// The path can be the game exe or the game folder.
+// Returns the other executables that could also be the game, so the user can pick a different one.
 #[tauri::command]
 #[specta::specta]
-async fn add_game(path: PathBuf, handle: AppHandle) -> Result {
+async fn add_game(path: PathBuf, handle: AppHandle) -> Result<Vec<PathBuf>> {
 	let state = handle.app_state();
 	let normalized_path = normalize_path(&path);
-	let game_id = hash_path(&normalized_path);
 
-	if state.installed_games.try_get(&game_id).is_ok() {
-		return Err(Error::GameAlreadyAdded(normalized_path));
+	let manual_provider::FoundGame {
+		game,
+		path: game_path,
+		other_candidates,
+	} = manual_provider::find_game(&normalized_path)?;
+
+	if state.installed_games.try_get(&game.id).is_ok() {
+		return Err(Error::GameAlreadyAdded(game_path));
 	}
 
-	let game = manual_provider::add_game(&normalized_path)?;
+	manual_provider::add_game(&game_path)?;
 	let game_name = game.name.clone();
 
 	let mut installed_games = state.installed_games.get_data()?.clone();
@@ -481,9 +1022,50 @@ async fn add_game(path: PathBuf, handle: AppHandle) -> Result {
 
 	analytics::send_event(analytics::Event::ManuallyAddGame, &game_name).await;
 
+	Ok(other_candidates)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_game_overrides(game_id: &str, overrides: GameOverrides, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let mut installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get_mut(game_id)?;
+
+	// Manual games keep their overrides in the same file as the game paths.
+	if game.provider == ProviderId::Manual {
+		manual_provider::set_game_overrides(&game.executable.path, &overrides)?;
+	} else {
+		game_overrides::set(game_id, &overrides)?;
+	}
+
+	// Values that stop being overridden only go back to the detected ones on the next full refresh,
+	// but the executable can be detected again right now.
+	game.set_overrides(&overrides);
+	game.refresh_executable()?;
+
+	update_state(
+		AppEvent::SyncInstalledGames,
+		installed_games,
+		&state.installed_games,
+		&handle,
+	);
+
 	Ok(())
 }
 
+// Puts the game folder back to how it was before we installed anything,
+// and removes all the mod data for this game.
+#[tauri::command]
+#[specta::specta]
+async fn restore_game_files(game_id: &str, handle: AppHandle) -> Result {
+	let game = handle.app_state().installed_games.try_get(game_id)?;
+
+	install_journal::restore(&game)?;
+
+	refresh_game_mods_and_exe(game_id, &handle)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn remove_game(game_id: &str, handle: AppHandle) -> Result {
@@ -529,10 +1111,47 @@ async fn run_provider_command(
 #[tauri::command]
 #[specta::specta]
 async fn delete_steam_appinfo_cache() -> Result {
-	let steam_dir = SteamDir::locate()?;
+	let steam_dir = steam_provider::locate_steam_dir()?;
 	steam::appinfo::delete(steam_dir.path())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn get_provider_settings() -> Result<provider_settings::Map> {
+	Ok(provider_settings::get_all())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_provider_settings(
+	provider_id: ProviderId,
+	settings: ProviderSettings,
+	handle: AppHandle,
+) -> Result {
+	provider_settings::set(provider_id, settings)?;
+
+	// Providers read their settings when they're created, so we need to set them all up again.
+	update_data(handle).await
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_database_sources() -> Result<Vec<ModDatabaseSource>> {
+	Ok(mod_database_sources::get_all())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_mod_database_sources(sources: Vec<ModDatabaseSource>, handle: AppHandle) -> Result {
+	mod_database_sources::set(&sources)?;
+
+	// Remote mods need to be fetched again from the new sources.
+	let resources_path = paths::resources_path(&handle)?;
+	update_mods(handle, resources_path).await;
+
+	Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn frontend_ready() -> Result {
@@ -551,7 +1170,12 @@ async fn open_logs_folder() -> Result {
 
 #[tauri::command]
 #[specta::specta]
-async fn dummy_command() -> Result<(InstalledGame, AppEvent, ProviderCommandAction)> {
+async fn dummy_command() -> Result<(
+	InstalledGame,
+	AppEvent,
+	ProviderCommandAction,
+	DownloadProgress,
+)> {
 	// This command is here just so tauri_specta exports these types.
 	// This should stop being needed once tauri_specta starts supporting events.
 	Err(Error::NotImplemented)
@@ -619,6 +1243,22 @@ fn main() {
 			open_game_folder,
 			install_mod,
 			uninstall_mod,
+			uninstall_mod_loader,
+			set_mod_enabled,
+			set_launch_vanilla,
+			get_mod_profiles,
+			create_mod_profile,
+			rename_mod_profile,
+			delete_mod_profile,
+			apply_mod_profile,
+			get_mod_updates,
+			get_mod_versions,
+			download_mod_release,
+			get_mod_changelog,
+			get_mod_release_assets,
+			install_mod_version,
+			pin_mod_version,
+			update_installed_mods,
 			open_game_mods_folder,
 			start_game,
 			start_game_exe,
@@ -636,6 +1276,16 @@ fn main() {
 			refresh_game,
 			open_logs_folder,
 			run_provider_command,
+			get_provider_settings,
+			set_provider_settings,
+			set_game_overrides,
+			get_disk_usage,
+			remove_orphaned_mod_data,
+			cancel_mod_download,
+			restore_game_files,
+			get_mod_database_sources,
+			import_mod,
+			set_mod_database_sources,
 		]
 	);
 
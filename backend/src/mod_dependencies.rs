@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::{
+	game_mod::ModRequirement,
+	installed_game::InstalledGame,
+	local_mod,
+	remote_mod,
+	Error,
+	Result,
+};
+
+// What we know about a mod that could be installed, from the local files or from the database.
+struct ModCandidate {
+	version: Option<String>,
+	dependencies: Vec<ModRequirement>,
+	conflicts: Vec<ModRequirement>,
+}
+
+struct Resolver<'a> {
+	game: &'a InstalledGame,
+	local_mods: &'a local_mod::Map,
+	remote_mods: &'a remote_mod::Map,
+	plan: Vec<String>,
+	visiting: Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+	// Local mods win, since that's what install_mod ends up installing if it's there.
+	fn get_candidate(&self, mod_id: &str) -> Option<ModCandidate> {
+		if let Some(local_mod) = self.local_mods.get(mod_id) {
+			let manifest = local_mod.data.manifest.as_ref();
+
+			return Some(ModCandidate {
+				version: manifest.map(|manifest| manifest.version.clone()),
+				dependencies: manifest
+					.map_or_else(Vec::default, |manifest| manifest.dependencies.clone()),
+				conflicts: manifest
+					.map_or_else(Vec::default, |manifest| manifest.conflicts.clone()),
+			});
+		}
+
+		let remote_mod = self.remote_mods.get(mod_id)?;
+
+		// Mods that are only on GitHub don't have a version until we ask GitHub for it.
+		Some(ModCandidate {
+			version: remote_mod
+				.data
+				.latest_version
+				.as_ref()
+				.map(|latest_version| latest_version.id.clone()),
+			dependencies: remote_mod.data.dependencies.clone(),
+			conflicts: remote_mod.data.conflicts.clone(),
+		})
+	}
+
+	fn is_installed(&self, requirement: &ModRequirement) -> bool {
+		self.game
+			.installed_mod_versions
+			.get(&requirement.id)
+			.map_or(false, |installed_mod| {
+				installed_mod.enabled && requirement.is_met_by(&installed_mod.version)
+			})
+	}
+
+	fn visit(&mut self, mod_id: &str) -> Result {
+		if self
+			.plan
+			.iter()
+			.any(|planned_mod_id| planned_mod_id == mod_id)
+		{
+			return Ok(());
+		}
+
+		if self
+			.visiting
+			.iter()
+			.any(|visiting_mod_id| visiting_mod_id == mod_id)
+		{
+			self.visiting.push(mod_id.to_string());
+			return Err(Error::ModDependencyCycle(self.visiting.join(" -> ")));
+		}
+
+		let Some(candidate) = self.get_candidate(mod_id) else {
+			// The mod being installed might be on disk without being in the app state yet.
+			// Installing it refreshes the local mods before giving up, so we let that happen,
+			// we just can't know its dependencies until then.
+			if self.visiting.is_empty() {
+				self.plan.push(mod_id.to_string());
+				return Ok(());
+			}
+
+			return Err(Error::DataEntryNotFound(mod_id.to_string()));
+		};
+
+		self.visiting.push(mod_id.to_string());
+
+		for dependency in &candidate.dependencies {
+			if self.is_installed(dependency) {
+				continue;
+			}
+
+			let is_dependency_available =
+				self.get_candidate(&dependency.id)
+					.map_or(false, |dependency_candidate| {
+						dependency_candidate.version.as_ref().map_or_else(
+							|| dependency.version.is_none(),
+							|version| dependency.is_met_by(version),
+						)
+					});
+
+			if !is_dependency_available {
+				return Err(Error::ModDependencyNotAvailable(
+					mod_id.to_string(),
+					dependency.to_string(),
+				));
+			}
+
+			self.visit(&dependency.id)?;
+		}
+
+		self.visiting.pop();
+		self.plan.push(mod_id.to_string());
+
+		Ok(())
+	}
+
+	// Checks conflicts both ways: mods we're about to install against everything else,
+	// and mods already installed against the ones we're about to install.
+	fn check_conflicts(&self) -> Result {
+		let mut present_versions: HashMap<String, String> = self
+			.game
+			.installed_mod_versions
+			.iter()
+			.filter(|(_, installed_mod)| installed_mod.enabled)
+			.map(|(mod_id, installed_mod)| (mod_id.clone(), installed_mod.version.clone()))
+			.collect();
+
+		let mut planned_candidates = Vec::new();
+		for mod_id in &self.plan {
+			if let Some(candidate) = self.get_candidate(mod_id) {
+				present_versions.insert(
+					mod_id.clone(),
+					candidate.version.clone().unwrap_or_default(),
+				);
+				planned_candidates.push((mod_id, candidate));
+			}
+		}
+
+		for (mod_id, candidate) in &planned_candidates {
+			for conflict in &candidate.conflicts {
+				if let Some(version) = present_versions.get(&conflict.id) {
+					if &conflict.id != *mod_id && conflict.is_met_by(version) {
+						return Err(Error::ModConflict((*mod_id).clone(), conflict.id.clone()));
+					}
+				}
+			}
+		}
+
+		for (installed_mod_id, manifest) in self.game.get_installed_manifests() {
+			if !present_versions.contains_key(&installed_mod_id)
+				|| self.plan.contains(&installed_mod_id)
+			{
+				continue;
+			}
+
+			for conflict in &manifest.conflicts {
+				if let Some((planned_mod_id, candidate)) = planned_candidates
+					.iter()
+					.find(|(planned_mod_id, _)| **planned_mod_id == conflict.id)
+				{
+					if conflict.is_met_by(candidate.version.as_deref().unwrap_or_default()) {
+						return Err(Error::ModConflict(
+							installed_mod_id,
+							(*planned_mod_id).clone(),
+						));
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// Returns the mods that need to be installed, in order, so that every dependency is installed before the mods that need it.
+// The mod itself is always last. Dependencies that are already installed are left out.
+pub fn resolve(
+	mod_id: &str,
+	game: &InstalledGame,
+	local_mods: &local_mod::Map,
+	remote_mods: &remote_mod::Map,
+) -> Result<Vec<String>> {
+	let mut resolver = Resolver {
+		game,
+		local_mods,
+		remote_mods,
+		plan: Vec::default(),
+		visiting: Vec::default(),
+	};
+
+	resolver.visit(mod_id)?;
+	resolver.check_conflicts()?;
+
+	Ok(resolver.plan)
+}
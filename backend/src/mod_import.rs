@@ -0,0 +1,276 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	archives,
+	files,
+	game_engines::{
+		game_engine::GameEngineBrand,
+		unity::UnityScriptingBackend,
+	},
+	game_mod::CommonModData,
+	local_mod,
+	maps::TryGettable,
+	mod_loaders::{
+		bepinex::BepInEx,
+		mod_loader::{
+			self,
+			ModLoaderActions,
+			ModLoaderStatic,
+		},
+		runnable_loader::RunnableLoader,
+	},
+	mod_manifest::{
+		self,
+		Manifest,
+	},
+	paths,
+	Error,
+	Result,
+};
+
+// Version used in generated manifests, since we have no way of knowing the real one.
+const IMPORTED_VERSION: &str = "imported";
+
+// Extensions that get stripped from archive names to make the mod id, like foo.tar.gz -> foo.
+const ARCHIVE_EXTENSIONS: [&str; 7] = ["zip", "7z", "tar", "gz", "tgz", "xz", "zst"];
+
+// How many nested folders we look through to find the mod, in case something is really weird.
+const MAX_MOD_ROOT_DEPTH: usize = 10;
+
+// Archives often have everything inside a single folder, or inside a BepInEx folder.
+// Symlinks aren't followed, since one pointing back up would keep us going in circles.
+fn find_mod_root(path: &Path) -> Result<PathBuf> {
+	let mut mod_root = path.to_path_buf();
+
+	for _ in 0..MAX_MOD_ROOT_DEPTH {
+		if local_mod::get_manifest_path(&mod_root).is_file()
+			|| mod_root.join("plugins").is_dir()
+			|| mod_root.join("patchers").is_dir()
+		{
+			return Ok(mod_root);
+		}
+
+		if is_real_dir(&mod_root.join("BepInEx")) {
+			mod_root = mod_root.join("BepInEx");
+			continue;
+		}
+
+		let entries: Vec<_> = fs::read_dir(&mod_root)?
+			.filter_map(|entry| Some(entry.ok()?.path()))
+			.collect();
+
+		match entries.as_slice() {
+			[single_folder] if is_real_dir(single_folder) => mod_root.clone_from(single_folder),
+			_ => break,
+		}
+	}
+
+	Err(Error::ModImportUnknownLayout(path.to_path_buf()))
+}
+
+fn is_real_dir(path: &Path) -> bool {
+	fs::symlink_metadata(path).map_or(false, |metadata| metadata.is_dir())
+}
+
+// IL2CPP BepInEx plugins reference one of these interop libraries, Mono ones don't.
+fn detect_unity_backend(mod_root: &Path) -> UnityScriptingBackend {
+	let is_il2cpp = paths::glob_path(&mod_root.join("**").join("*.dll"))
+		.iter()
+		.filter_map(|dll_path| fs::read(dll_path).ok())
+		.any(|dll_bytes| {
+			[b"Il2CppInterop".as_slice(), b"UnhollowerBaseLib".as_slice()]
+				.iter()
+				.any(|library_name| {
+					dll_bytes
+						.windows(library_name.len())
+						.any(|window| window == *library_name)
+				})
+		});
+
+	if is_il2cpp {
+		UnityScriptingBackend::Il2Cpp
+	} else {
+		UnityScriptingBackend::Mono
+	}
+}
+
+// Archives like foo.tar.gz have more than one extension, and none of them should end up in the id.
+fn get_default_mod_id(path: &Path) -> Result<String> {
+	let mut mod_id = path
+		.file_name()
+		.ok_or_else(|| Error::PathParseFailure(path.to_path_buf()))?
+		.to_string_lossy()
+		.to_string();
+
+	if path.is_dir() {
+		return Ok(mod_id);
+	}
+
+	while let Some((stem, extension)) = mod_id.rsplit_once('.') {
+		if stem.is_empty() || !ARCHIVE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+		{
+			break;
+		}
+		mod_id = stem.to_string();
+	}
+
+	Ok(mod_id)
+}
+
+// The id ends up as a folder name, so it can't be a path.
+fn validate_mod_id(mod_id: &str) -> Result {
+	if paths::is_single_component(mod_id) {
+		Ok(())
+	} else {
+		Err(Error::InvalidModId(mod_id.to_string()))
+	}
+}
+
+// Takes a mod archive or folder, and copies it to where its mod loader expects it.
+// Fails if the mod already exists, unless `overwrite` is set.
+// Returns the id of the imported mod.
+pub fn import(path: &Path, overwrite: bool, mod_loaders: &mod_loader::Map) -> Result<String> {
+	let default_mod_id = get_default_mod_id(path)?;
+
+	if path.is_dir() {
+		return import_folder(&default_mod_id, path, overwrite, mod_loaders);
+	}
+
+	let extract_path = paths::installed_mods_path()?
+		.join("imports")
+		.join(uuid::Uuid::new_v4().hyphenated().to_string());
+
+	let result = archives::extract(path, &extract_path)
+		.and_then(|_| import_folder(&default_mod_id, &extract_path, overwrite, mod_loaders));
+
+	// Extracting can fail halfway, so this needs to happen whether it worked or not.
+	if extract_path.is_dir() {
+		if let Err(err) = fs::remove_dir_all(&extract_path) {
+			error!(
+				"Failed to remove temporary import folder `{}`. Error: {err}",
+				extract_path.display()
+			);
+		}
+	}
+
+	result
+}
+
+fn import_folder(
+	default_mod_id: &str,
+	source_path: &Path,
+	overwrite: bool,
+	mod_loaders: &mod_loader::Map,
+) -> Result<String> {
+	let mod_root = find_mod_root(source_path)?;
+	let existing_manifest = mod_manifest::get(&local_mod::get_manifest_path(&mod_root));
+
+	let mod_id = existing_manifest
+		.as_ref()
+		.and_then(|manifest| manifest.id.clone())
+		.unwrap_or_else(|| default_mod_id.to_string());
+
+	validate_mod_id(&mod_id)?;
+
+	let manifest = existing_manifest.clone().unwrap_or_else(|| Manifest {
+		id: Some(mod_id.clone()),
+		version: IMPORTED_VERSION.to_string(),
+		runnable: None,
+		engine: Some(GameEngineBrand::Unity),
+		unity_backend: Some(detect_unity_backend(&mod_root)),
+		dependencies: Vec::default(),
+		conflicts: Vec::default(),
+	});
+
+	let loader_id = if manifest.runnable.is_some() {
+		RunnableLoader::ID
+	} else {
+		BepInEx::ID
+	};
+
+	// Older manifests might not say which backend a BepInEx mod is for.
+	let unity_backend = if loader_id == BepInEx::ID {
+		manifest
+			.unity_backend
+			.or_else(|| Some(detect_unity_backend(&mod_root)))
+	} else {
+		manifest.unity_backend
+	};
+
+	let mod_loader = mod_loaders.try_get(loader_id)?;
+	let target_path = mod_loader.get_mod_path(&CommonModData {
+		id: mod_id.clone(),
+		engine: manifest.engine,
+		unity_backend,
+		loader_id: loader_id.to_string(),
+	})?;
+
+	if target_path.is_dir() {
+		if !overwrite {
+			return Err(Error::ModAlreadyExists(mod_id));
+		}
+
+		// Importing from the mod's own folder would delete what we're importing.
+		if mod_root
+			.canonicalize()?
+			.starts_with(target_path.canonicalize()?)
+		{
+			return Err(Error::ModImportFromTarget(mod_root));
+		}
+	}
+
+	copy_to_target(
+		&mod_root,
+		&target_path,
+		existing_manifest.is_none().then_some(&manifest),
+	)?;
+
+	Ok(mod_id)
+}
+
+// Copies to a temporary folder first, so the current version of the mod is only replaced once the copy worked.
+fn copy_to_target(
+	mod_root: &Path,
+	target_path: &Path,
+	generated_manifest: Option<&Manifest>,
+) -> Result {
+	let temporary_path = paths::installed_mods_path()?
+		.join("imports")
+		.join(uuid::Uuid::new_v4().hyphenated().to_string());
+
+	let result = files::copy_dir_all(mod_root, &temporary_path).and_then(|_| {
+		if let Some(manifest) = generated_manifest {
+			fs::write(
+				local_mod::get_manifest_path(&temporary_path),
+				serde_json::to_string_pretty(manifest)?,
+			)?;
+		}
+
+		if target_path.is_dir() {
+			fs::remove_dir_all(target_path)?;
+		}
+		fs::create_dir_all(paths::path_parent(target_path)?)?;
+		fs::rename(&temporary_path, target_path)?;
+
+		Ok(())
+	});
+
+	if result.is_err() && temporary_path.is_dir() {
+		if let Err(err) = fs::remove_dir_all(&temporary_path) {
+			error!(
+				"Failed to remove temporary import folder `{}`. Error: {err}",
+				temporary_path.display()
+			);
+		}
+	}
+
+	result
+}
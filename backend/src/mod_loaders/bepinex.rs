@@ -1,9 +1,6 @@
 use std::{
 	collections::HashMap,
-	fs::{
-		self,
-		File,
-	},
+	fs,
 	path::{
 		Path,
 		PathBuf,
@@ -11,11 +8,10 @@ use std::{
 };
 
 use async_trait::async_trait;
-use zip::ZipArchive;
 
 use super::mod_loader::ModLoaderStatic;
 use crate::{
-	files::copy_dir_all,
+	archives,
 	game_engines::{
 		game_engine::{
 			GameEngine,
@@ -24,14 +20,19 @@ use crate::{
 		unity::UnityScriptingBackend,
 	},
 	game_mod::CommonModData,
+	install_journal,
 	installed_game::InstalledGame,
 	local_mod::{
 		LocalMod,
 		ModKind,
 	},
-	mod_loaders::mod_loader::{
-		ModLoaderActions,
-		ModLoaderData,
+	mod_loaders::{
+		doorstop,
+		mod_database::ModDownload,
+		mod_loader::{
+			ModLoaderActions,
+			ModLoaderData,
+		},
 	},
 	paths,
 	serializable_struct,
@@ -104,37 +105,47 @@ impl ModLoaderActions for BepInEx {
 		let mod_loader_archive = architecture_path.join("mod-loader.zip");
 		let folder_to_copy_to_game = architecture_path.join("copy-to-game");
 		let game_data_folder = &game.get_installed_mods_folder()?;
+		let game_folder = paths::path_parent(&game.executable.path)?;
 
-		ZipArchive::new(File::open(mod_loader_archive)?)?.extract(game_data_folder)?;
+		// Everything that gets written to the game folder goes through the install journal,
+		// so that a failed install doesn't leave the game half-modded.
+		install_journal::run(game, |transaction| {
+			let mut installed_files = archives::extract(&mod_loader_archive, game_data_folder)?;
 
-		let game_folder = paths::path_parent(&game.executable.path)?;
+			installed_files.extend(transaction.copy_dir_all(&folder_to_copy_to_game, game_folder)?);
 
-		copy_dir_all(folder_to_copy_to_game, game_folder)?;
+			let is_legacy = game.executable.engine.as_ref().map_or(false, is_legacy);
 
-		let is_legacy = game.executable.engine.as_ref().map_or(false, is_legacy);
+			let config_origin_path = &self.data.path.join("config").join(if is_legacy {
+				"BepInEx-legacy.cfg"
+			} else {
+				"BepInEx.cfg"
+			});
 
-		let config_origin_path = &self.data.path.join("config").join(if is_legacy {
-			"BepInEx-legacy.cfg"
-		} else {
-			"BepInEx.cfg"
-		});
+			let config_target_folder = game_data_folder.join("BepInEx").join("config");
 
-		let config_target_folder = game_data_folder.join("BepInEx").join("config");
+			fs::create_dir_all(&config_target_folder)?;
 
-		fs::create_dir_all(&config_target_folder)?;
+			let config_target_path = config_target_folder.join("BepInEx.cfg");
+			transaction.copy(config_origin_path, &config_target_path)?;
+			installed_files.push(config_target_path);
 
-		fs::copy(config_origin_path, config_target_folder.join("BepInEx.cfg"))?;
+			let doorstop_config =
+				fs::read_to_string(scripting_backend_path.join(doorstop::CONFIG_FILE_NAME))?;
 
-		let doorstop_config =
-			fs::read_to_string(scripting_backend_path.join("doorstop_config.ini"))?;
+			// Installing also turns off "launch vanilla", since the template has doorstop enabled.
+			let doorstop_config_path = game_folder.join(doorstop::CONFIG_FILE_NAME);
+			transaction.write(
+				&doorstop_config_path,
+				doorstop_config.replace(
+					"{{MOD_FILES_PATH}}",
+					game_data_folder.to_string_lossy().as_ref(),
+				),
+			)?;
+			installed_files.push(doorstop_config_path);
 
-		fs::write(
-			game_folder.join("doorstop_config.ini"),
-			doorstop_config.replace(
-				"{{MOD_FILES_PATH}}",
-				game_data_folder.to_string_lossy().as_ref(),
-			),
-		)?;
+			game.save_installed_files(Self::ID, &installed_files)
+		})?;
 
 		// TODO: linux stuff
 		// if let Some(operating_system) = game.executable.operating_system {
@@ -150,28 +161,174 @@ impl ModLoaderActions for BepInEx {
 		Ok(())
 	}
 
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
+	fn uninstall(&self, game: &InstalledGame) -> Result {
+		for manifest_path in game.get_manifest_paths() {
+			if let Some(mod_id) = manifest_path.file_stem().and_then(|stem| stem.to_str()) {
+				self.uninstall_mod(game, mod_id)?;
+			}
+		}
+
+		// Files in the game folder get restored to how they were before we installed BepInEx.
+		// Installs from before we kept track of installed files won't have a list,
+		// so we also look for the doorstop files we know about.
+		let game_folder = paths::path_parent(&game.executable.path)?;
+		let mut game_folder_files: Vec<_> = game
+			.get_installed_files(Self::ID)?
+			.into_iter()
+			.filter(|file| file.starts_with(game_folder))
+			.collect();
+
+		// Some games ship their own winhttp.dll or doorstop, so we only touch those files
+		// if it looks like we're the ones who put them there.
+		let game_data_folder = game.get_installed_mods_folder()?;
+		let bepinex_folder = game_data_folder.join("BepInEx");
+		if bepinex_folder.is_dir()
+			|| doorstop::targets_folder(&game.executable.path, &game_data_folder)
+		{
+			for file_name in DOORSTOP_FILE_NAMES {
+				let file = game_folder.join(file_name);
+				if !game_folder_files.contains(&file) {
+					game_folder_files.push(file);
+				}
+			}
+		}
+
+		install_journal::restore_files(game, &game_folder_files)?;
+
+		game.remove_installed_mod_data(Self::ID)?;
+
+		if bepinex_folder.is_dir() {
+			fs::remove_dir_all(bepinex_folder)?;
+		}
+
+		Ok(())
+	}
+
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		for (enabled_folder, disabled_folder) in get_mod_folders(game, mod_id)? {
+			if enabled_folder.is_dir() {
+				fs::remove_dir_all(enabled_folder)?;
+			}
+
+			if disabled_folder.is_dir() {
+				fs::remove_dir_all(disabled_folder)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	// Disabled mods are moved to a folder BepInEx doesn't look into.
+	fn set_mod_enabled_inner(&self, game: &InstalledGame, mod_id: &str, enabled: bool) -> Result {
+		for (enabled_folder, disabled_folder) in get_mod_folders(game, mod_id)? {
+			let (from, to) = if enabled {
+				(disabled_folder, enabled_folder)
+			} else {
+				(enabled_folder, disabled_folder)
+			};
+
+			if !from.is_dir() {
+				continue;
+			}
+
+			if to.is_dir() {
+				fs::remove_dir_all(&to)?;
+			}
+			fs::create_dir_all(paths::path_parent(&to)?)?;
+			fs::rename(from, to)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_enabled(&self, game: &InstalledGame, enabled: bool) -> Result {
+		let doorstop_config_path = doorstop::get_config_path(&game.executable.path)?;
+		if !doorstop_config_path.is_file() {
+			return Ok(());
+		}
+
+		let doorstop_config = fs::read_to_string(&doorstop_config_path)?;
+
+		install_journal::run(game, |transaction| {
+			transaction.write(
+				&doorstop_config_path,
+				doorstop::set_enabled(&doorstop_config, enabled),
+			)
+		})
+	}
+
+	async fn install_mod_inner(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+	) -> Result<Vec<PathBuf>> {
 		self.install(game)?;
 
+		// Otherwise an old disabled version could come back if the mod gets disabled and enabled again.
+		for (_, disabled_folder) in get_mod_folders(game, &local_mod.common.id)? {
+			if disabled_folder.is_dir() {
+				fs::remove_dir_all(disabled_folder)?;
+			}
+		}
+
 		let bepinex_folder = game.get_installed_mods_folder()?.join("BepInEx");
 
-		let mod_plugin_path = local_mod.data.path.join("plugins");
-		if mod_plugin_path.is_dir() {
-			copy_dir_all(
-				mod_plugin_path,
-				bepinex_folder.join("plugins").join(&local_mod.common.id),
-			)?;
+		install_journal::run(game, |transaction| {
+			let mut installed_files = Vec::new();
+
+			let mod_plugin_path = local_mod.data.path.join("plugins");
+			if mod_plugin_path.is_dir() {
+				installed_files.extend(transaction.copy_dir_all(
+					&mod_plugin_path,
+					&bepinex_folder.join("plugins").join(&local_mod.common.id),
+				)?);
+			}
+
+			let mod_patch_path = local_mod.data.path.join("patchers");
+			if mod_patch_path.is_dir() {
+				installed_files.extend(transaction.copy_dir_all(
+					&mod_patch_path,
+					&bepinex_folder.join("patchers").join(&local_mod.common.id),
+				)?);
+			}
+
+			Ok(installed_files)
+		})
+	}
+
+	// Mods without a root (like Thunderstore packages) can come with a BepInEx folder,
+	// or with the plugin files loose in the archive. Either way we need plugins/patchers folders.
+	fn get_extracted_mod_files_path(
+		&self,
+		extract_path: &Path,
+		mod_download: &ModDownload,
+	) -> Result<PathBuf> {
+		if let Some(root) = &mod_download.root {
+			return Ok(extract_path.join(root));
 		}
 
-		let mod_patch_path = local_mod.data.path.join("patchers");
-		if mod_patch_path.is_dir() {
-			copy_dir_all(
-				mod_patch_path,
-				bepinex_folder.join("patchers").join(&local_mod.common.id),
-			)?;
+		let bepinex_path = extract_path.join("BepInEx");
+		if bepinex_path.is_dir() {
+			return Ok(bepinex_path);
 		}
 
-		Ok(())
+		let plugins_path = extract_path.join("plugins");
+		if plugins_path.is_dir() || extract_path.join("patchers").is_dir() {
+			return Ok(extract_path.to_path_buf());
+		}
+
+		let loose_paths: Vec<_> = fs::read_dir(extract_path)?
+			.filter_map(|entry| Some(entry.ok()?.path()))
+			.collect();
+
+		fs::create_dir_all(&plugins_path)?;
+		for loose_path in loose_paths {
+			if let Some(file_name) = loose_path.file_name() {
+				fs::rename(&loose_path, plugins_path.join(file_name))?;
+			}
+		}
+
+		Ok(extract_path.to_path_buf())
 	}
 
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
@@ -198,6 +355,27 @@ impl ModLoaderActions for BepInEx {
 	}
 }
 
+// Files that doorstop needs in the game folder, so that BepInEx gets loaded.
+const DOORSTOP_FILE_NAMES: [&str; 2] = ["winhttp.dll", doorstop::CONFIG_FILE_NAME];
+
+// Pairs of (enabled, disabled) folders for each kind of folder a mod can have.
+fn get_mod_folders(game: &InstalledGame, mod_id: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+	let bepinex_folder = game.get_installed_mods_folder()?.join("BepInEx");
+
+	Ok(["plugins", "patchers"]
+		.iter()
+		.map(|folder_name| {
+			(
+				bepinex_folder.join(folder_name).join(mod_id),
+				bepinex_folder
+					.join("disabled")
+					.join(folder_name)
+					.join(mod_id),
+			)
+		})
+		.collect())
+}
+
 const fn is_legacy(engine: &GameEngine) -> bool {
 	if let Some(version) = &engine.version {
 		version.major < 5 || (version.major == 5 && version.minor < 5)
@@ -0,0 +1,67 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use crate::{
+	paths,
+	Result,
+};
+
+// Doorstop is what makes the game load BepInEx. It reads this config from the game folder.
+pub const CONFIG_FILE_NAME: &str = "doorstop_config.ini";
+
+const ENABLED_KEY: &str = "enabled=";
+const TARGET_ASSEMBLY_KEY: &str = "targetAssembly=";
+
+pub fn get_config_path(game_executable_path: &Path) -> Result<PathBuf> {
+	Ok(paths::path_parent(game_executable_path)?.join(CONFIG_FILE_NAME))
+}
+
+// Doorstop is enabled by default, so anything other than an explicit "false" counts as enabled.
+pub fn is_enabled(game_executable_path: &Path) -> bool {
+	get_config_path(game_executable_path)
+		.and_then(|config_path| Ok(fs::read_to_string(config_path)?))
+		.map_or(true, |config| {
+			!config.lines().any(|line| {
+				line.trim()
+					.strip_prefix(ENABLED_KEY)
+					.map_or(false, |value| value.trim().eq_ignore_ascii_case("false"))
+			})
+		})
+}
+
+// True if the config in the game folder makes doorstop load an assembly from inside the given folder.
+pub fn targets_folder(game_executable_path: &Path, folder: &Path) -> bool {
+	let folder_string = folder.to_string_lossy();
+
+	get_config_path(game_executable_path)
+		.and_then(|config_path| Ok(fs::read_to_string(config_path)?))
+		.map_or(false, |config| {
+			config.lines().any(|line| {
+				line.trim()
+					.strip_prefix(TARGET_ASSEMBLY_KEY)
+					.map_or(false, |value| {
+						value.trim().starts_with(folder_string.as_ref())
+					})
+			})
+		})
+}
+
+pub fn set_enabled(config: &str, enabled: bool) -> String {
+	config
+		.split('\n')
+		.map(|line| {
+			if line.trim_start().starts_with(ENABLED_KEY) {
+				let line_ending = if line.ends_with('\r') { "\r" } else { "" };
+				format!("{ENABLED_KEY}{enabled}{line_ending}")
+			} else {
+				line.to_string()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
@@ -1,4 +1,7 @@
 pub mod bepinex;
+pub mod doorstop;
 pub mod mod_database;
+pub mod mod_database_sources;
 pub mod mod_loader;
 pub mod runnable_loader;
+pub mod thunderstore;
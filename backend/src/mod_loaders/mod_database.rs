@@ -1,19 +1,56 @@
-use std::path::PathBuf;
+use std::{
+	fs,
+	path::{
+		Component,
+		Path,
+		PathBuf,
+	},
+};
 
-use lazy_regex::regex_captures;
-use log::error;
-use reqwest::redirect::Policy;
+use lazy_regex::{
+	regex,
+	regex_captures,
+};
+use log::{
+	error,
+	warn,
+};
+use minisign_verify::{
+	PublicKey,
+	Signature,
+};
+use reqwest::{
+	redirect::Policy,
+	StatusCode,
+};
 
+use super::{
+	bepinex::BepInEx,
+	mod_database_sources::{
+		ModDatabaseSource,
+		ModDatabaseSourceKind,
+	},
+	mod_loader::ModLoaderStatic,
+	thunderstore,
+};
 use crate::{
 	game_engines::{
 		game_engine::GameEngineBrand,
 		unity::UnityScriptingBackend,
 	},
+	game_mod::ModRequirement,
+	paths,
 	serializable_struct,
+	Error,
 	Result,
 };
 
-const URL_BASE: &str = "https://raicuparta.github.io/rai-pal-db";
+pub const URL_BASE: &str = "https://raicuparta.github.io/rai-pal-db";
+
+// Minisign public key used to verify the database files, which are signed next to each json ({URL_BASE}/{id}.json.minisig).
+// This key is only for the database, app updates are signed with a different one (see tauri.conf.json).
+// Only the official database is signed, other sources are trusted as they are.
+const DATABASE_PUBLIC_KEY: &str = "RWTxnrXiuYiCJOir3Hx/9oSMZl9/Nnk3IGNCI67DWWnmJLH70QjhdcKl";
 
 serializable_struct!(DatabaseEntry {
 	pub id: String,
@@ -26,6 +63,10 @@ serializable_struct!(DatabaseEntry {
 	pub unity_backend: Option<UnityScriptingBackend>,
 	pub github: Option<ModGithubInfo>,
 	pub redownload_id: Option<i32>,
+	#[serde(default)]
+	pub dependencies: Vec<ModRequirement>,
+	#[serde(default)]
+	pub conflicts: Vec<ModRequirement>,
 });
 
 serializable_struct!(RunnableModData {
@@ -33,15 +74,101 @@ serializable_struct!(RunnableModData {
 	pub args: Vec<String>,
 });
 
+// Needs to be bumped every time the database format changes in a way older versions of the app can't read.
+pub const SCHEMA_VERSION: u32 = 1;
+
 serializable_struct!(ModDatabase {
+  // Databases from before the schema was versioned don't have this.
+  #[serde(default)]
+  pub schema_version: u32,
   pub mods: Vec<DatabaseEntry>,
 });
 
+// Entries are kept as raw json at first, so that a broken entry doesn't break the whole database.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawModDatabase {
+	#[serde(default)]
+	schema_version: u32,
+	mods: Vec<serde_json::Value>,
+}
+
+// Returns the database with all the valid entries, plus an error for each entry that couldn't be parsed.
+fn parse(database_name: &str, bytes: &[u8]) -> Result<(ModDatabase, Vec<Error>)> {
+	let raw_database = serde_json::from_slice::<RawModDatabase>(bytes)?;
+
+	if raw_database.schema_version > SCHEMA_VERSION {
+		return Err(Error::ModDatabaseSchemaTooNew(
+			database_name.to_string(),
+			raw_database.schema_version,
+			SCHEMA_VERSION,
+		));
+	}
+
+	let mut mods = Vec::new();
+	let mut errors = Vec::new();
+
+	for (index, raw_entry) in raw_database.mods.into_iter().enumerate() {
+		let mod_id = raw_entry
+			.get("id")
+			.and_then(serde_json::Value::as_str)
+			.unwrap_or("unknown")
+			.to_string();
+
+		match serde_path_to_error::deserialize::<_, DatabaseEntry>(raw_entry) {
+			Ok(entry) => mods.push(entry),
+			Err(error) => {
+				errors.push(Error::InvalidModDatabaseEntry(
+					mod_id,
+					format!(
+						"at `mods[{index}].{}` in `{database_name}`: {}",
+						error.path(),
+						error.inner()
+					),
+				));
+			}
+		}
+	}
+
+	Ok((
+		ModDatabase {
+			schema_version: raw_database.schema_version,
+			mods,
+		},
+		errors,
+	))
+}
+
 serializable_struct!(ModDownload {
 	pub id: String,
 	pub url: String,
 	pub root: Option<PathBuf>,
 	pub runnable: Option<RunnableModData>,
+	// If present, the downloaded file is checked against these before being extracted.
+	pub sha256: Option<String>,
+	pub size: Option<u64>,
+});
+
+impl ModDownload {
+	// Download ids are release tags, but the latest version can also have a redownload id after a slash.
+	// See `DatabaseEntry::get_download`.
+	pub fn is_release(&self, tag: &str) -> bool {
+		self.id == tag
+			|| self
+				.id
+				.rsplit_once('/')
+				.is_some_and(|(release_tag, redownload_id)| {
+					release_tag == tag && redownload_id.parse::<i32>().is_ok()
+				})
+	}
+}
+
+serializable_struct!(ModRelease {
+	pub tag: String,
+	pub title: String,
+	pub updated_at: String,
+	// Release notes as HTML, the way GitHub renders them.
+	pub notes: String,
 });
 
 serializable_struct!(ModGithubInfo {
@@ -50,20 +177,255 @@ serializable_struct!(ModGithubInfo {
 	pub asset_name: String,
 	pub root: Option<PathBuf>,
 	pub runnable: Option<RunnableModData>,
+	// Release that sha256 and size refer to. They're only used when downloading this exact release,
+	// so a new release without updating the database just means the download isn't checked.
+	pub tag: Option<String>,
+	pub sha256: Option<String>,
+	pub size: Option<u64>,
 });
 
-pub async fn get(mod_loader_id: &str) -> Result<ModDatabase> {
+fn get_empty() -> (ModDatabase, Vec<Error>) {
+	(
+		ModDatabase {
+			schema_version: SCHEMA_VERSION,
+			mods: Vec::new(),
+		},
+		Vec::new(),
+	)
+}
+
+enum SourceLocation {
+	Remote(String),
+	Local(PathBuf),
+}
+
+// Remote sources need https, since what they point to ends up being run next to game executables.
+// Anything else is a file:// url, or a plain path.
+fn get_source_location(url: &str) -> Result<SourceLocation> {
+	let invalid_source =
+		|reason: &str| Error::InvalidModDatabaseSource(url.to_string(), reason.to_string());
+	let lowercase_url = url.to_ascii_lowercase();
+
+	if lowercase_url.starts_with("https://") {
+		Ok(SourceLocation::Remote(url.to_string()))
+	} else if lowercase_url.starts_with("http://") {
+		Err(invalid_source("only https urls are allowed"))
+	} else if lowercase_url.starts_with("file://") {
+		reqwest::Url::parse(url)
+			.map_err(|err| invalid_source(&err.to_string()))?
+			.to_file_path()
+			.map(SourceLocation::Local)
+			.map_err(|()| invalid_source("url doesn't point to a local path"))
+	} else {
+		Ok(SourceLocation::Local(PathBuf::from(url)))
+	}
+}
+
+async fn get_thunderstore(
+	source: &ModDatabaseSource,
+	mod_loader_id: &str,
+) -> Result<(ModDatabase, Vec<Error>)> {
+	if mod_loader_id != BepInEx::ID {
+		return Ok(get_empty());
+	}
+
+	let index_bytes = match get_source_location(&source.url)? {
+		SourceLocation::Remote(url) => thunderstore::get_index_bytes(&url).await?,
+		SourceLocation::Local(path) => fs::read(path)?,
+	};
+
+	thunderstore::parse_index(
+		&source.url,
+		&index_bytes,
+		source.unity_backend.unwrap_or(UnityScriptingBackend::Mono),
+	)
+}
+
+pub async fn get(
+	source: &ModDatabaseSource,
+	mod_loader_id: &str,
+) -> Result<(ModDatabase, Vec<Error>)> {
+	if source.kind == ModDatabaseSourceKind::Thunderstore {
+		return get_thunderstore(source, mod_loader_id).await;
+	}
+
+	let empty_database = get_empty();
+
+	let source_url = match get_source_location(source.url.trim_end_matches('/'))? {
+		SourceLocation::Remote(source_url) => source_url,
+		SourceLocation::Local(folder) => {
+			let path = folder.join(format!("{mod_loader_id}.json"));
+
+			// Sources don't need to have a database for every mod loader.
+			if !path.is_file() {
+				return Ok(empty_database);
+			}
+
+			return parse(&path.to_string_lossy(), &fs::read(&path)?);
+		}
+	};
+
 	let random = rand::random::<u32>();
+	let url = format!("{source_url}/{mod_loader_id}.json");
 
-	Ok(reqwest::get(format!(
-		"{URL_BASE}/{mod_loader_id}.json?cache_avoider={random}"
-	))
-	.await?
-	.json::<ModDatabase>()
-	.await?)
+	let response = reqwest::get(format!("{url}?cache_avoider={random}")).await?;
+	if response.status() == StatusCode::NOT_FOUND && source_url != URL_BASE {
+		return Ok(empty_database);
+	}
+
+	let database_bytes = response.error_for_status()?.bytes().await?;
+
+	if source_url != URL_BASE {
+		return parse(&url, &database_bytes);
+	}
+
+	// A missing or invalid signature fails the whole database, we don't fall back to using it unchecked.
+	let signature_response = reqwest::get(format!("{url}.minisig?cache_avoider={random}")).await?;
+	if signature_response.status() == StatusCode::NOT_FOUND {
+		return Err(Error::ModDatabaseSignatureMissing(url));
+	}
+
+	let signature = signature_response.error_for_status()?.text().await?;
+
+	Signature::decode(&signature)
+		.and_then(|signature| {
+			PublicKey::from_base64(DATABASE_PUBLIC_KEY)?.verify(&database_bytes, &signature, false)
+		})
+		.map_err(|error| Error::ModDatabaseSignatureInvalid(url.clone(), error.to_string()))?;
+
+	parse(&url, &database_bytes)
+}
+
+// Lets mod authors test database entries before publishing them.
+// Entries in {app_data}/mod-database-overrides/{mod_loader_id}.json replace remote entries with the same id.
+pub fn get_overrides_path(mod_loader_id: &str) -> Result<PathBuf> {
+	Ok(paths::app_data_path()?
+		.join("mod-database-overrides")
+		.join(format!("{mod_loader_id}.json")))
+}
+
+pub fn get_overrides(mod_loader_id: &str) -> Result<Option<(ModDatabase, Vec<Error>)>> {
+	let overrides_path = get_overrides_path(mod_loader_id)?;
+
+	if !overrides_path.is_file() {
+		return Ok(None);
+	}
+
+	Ok(Some(parse(
+		&overrides_path.to_string_lossy(),
+		&fs::read(&overrides_path)?,
+	)?))
+}
+
+// Paths inside the mod archive shouldn't be able to point outside of it.
+fn is_relative_inner_path(path: &Path) -> bool {
+	path.components()
+		.all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn get_download_paths_error(
+	root: Option<&PathBuf>,
+	runnable: Option<&RunnableModData>,
+) -> Option<String> {
+	if let Some(root) = root {
+		if !is_relative_inner_path(root) {
+			return Some(format!(
+				"`root` must be a relative path inside the archive, but it's `{}`",
+				root.display()
+			));
+		}
+	}
+
+	if let Some(runnable) = runnable {
+		if runnable.path.is_empty() || !is_relative_inner_path(Path::new(&runnable.path)) {
+			return Some(format!(
+				"`runnable.path` must be a relative path inside the mod, but it's `{}`",
+				runnable.path
+			));
+		}
+	}
+
+	None
 }
 
 impl DatabaseEntry {
+	pub fn validate(&self) -> Result {
+		self.get_validation_error().map_or(Ok(()), |error| {
+			Err(Error::InvalidModDatabaseEntry(self.id.clone(), error))
+		})
+	}
+
+	fn get_validation_error(&self) -> Option<String> {
+		if self.id.is_empty() {
+			return Some("`id` can't be empty".to_string());
+		}
+
+		// Ids are used for folder and file names.
+		if !paths::is_single_component(&self.id) {
+			return Some(format!(
+				"`id` must work as a folder name, but it's `{}`",
+				self.id
+			));
+		}
+
+		if self.latest_version.is_none() && self.github.is_none() {
+			return Some("needs either `latestVersion` or `github`".to_string());
+		}
+
+		if self
+			.redownload_id
+			.is_some_and(|redownload_id| redownload_id < 0)
+		{
+			return Some("`redownloadId` can't be negative".to_string());
+		}
+
+		if let Some(latest_version) = &self.latest_version {
+			if latest_version.id.is_empty() {
+				return Some("`latestVersion.id` can't be empty".to_string());
+			}
+
+			if !latest_version.url.starts_with("https://")
+				&& !latest_version.url.starts_with("http://")
+			{
+				return Some(format!(
+					"`latestVersion.url` must be an http(s) url, but it's `{}`",
+					latest_version.url
+				));
+			}
+
+			if let Some(error) = get_download_paths_error(
+				latest_version.root.as_ref(),
+				latest_version.runnable.as_ref(),
+			) {
+				return Some(error);
+			}
+		}
+
+		if let Some(github) = &self.github {
+			if github.user.is_empty() || github.repo.is_empty() || github.asset_name.is_empty() {
+				return Some(
+					"`github.user`, `github.repo` and `github.assetName` can't be empty"
+						.to_string(),
+				);
+			}
+
+			if (github.sha256.is_some() || github.size.is_some()) && github.tag.is_none() {
+				return Some(
+					"`github.sha256` and `github.size` need `github.tag`, the release they belong to"
+						.to_string(),
+				);
+			}
+
+			if let Some(error) =
+				get_download_paths_error(github.root.as_ref(), github.runnable.as_ref())
+			{
+				return Some(error);
+			}
+		}
+
+		None
+	}
+
 	pub async fn get_download(&self) -> Option<ModDownload> {
 		self.get_download_inner().await.map(|mut download| {
 			if let Some(redownload_id) = self.redownload_id {
@@ -142,17 +504,91 @@ impl ModGithubInfo {
 	}
 
 	pub async fn get_download(&self) -> Option<ModDownload> {
-		self.get_latest_tag().await.map(|latest_tag| ModDownload {
-			id: latest_tag.clone(),
+		self.get_latest_tag()
+			.await
+			.map(|latest_tag| self.get_release_download(&latest_tag, None))
+	}
+
+	// Hashes in the database only refer to one release and asset, so other ones can't be verified.
+	pub fn get_release_download(&self, tag: &str, asset_name: Option<&str>) -> ModDownload {
+		let is_verified_asset = self.tag.as_deref() == Some(tag)
+			&& asset_name.map_or(true, |asset_name| asset_name == self.asset_name);
+
+		if !is_verified_asset && (self.sha256.is_some() || self.size.is_some()) {
+			warn!(
+				"Database hash for `{}/{}` is for tag {:?}, so release `{tag}` won't be verified.",
+				self.user, self.repo, self.tag
+			);
+		}
+
+		ModDownload {
+			id: tag.to_string(),
 			root: self.root.clone(),
 			runnable: self.runnable.clone(),
+			sha256: self.sha256.clone().filter(|_| is_verified_asset),
+			size: self.size.filter(|_| is_verified_asset),
 			url: format!(
 				"{}/download/{}/{}",
 				self.get_releases_url(),
-				latest_tag,
-				self.asset_name
+				tag,
+				asset_name.unwrap_or(&self.asset_name)
 			),
-		})
+		}
+	}
+
+	// The releases Atom feed doesn't need an API token, but it only has the most recent releases.
+	pub async fn get_releases(&self) -> Result<Vec<ModRelease>> {
+		let feed = reqwest::get(format!("{}.atom", self.get_releases_url()))
+			.await?
+			.error_for_status()?
+			.text()
+			.await?;
+
+		Ok(regex!(r"(?s)<entry>(.*?)</entry>")
+			.captures_iter(&feed)
+			.filter_map(|entry_captures| {
+				let entry = entry_captures.get(1)?.as_str();
+
+				let (_, tag) =
+					regex_captures!(r#"<link[^>]*href="[^"]*/releases/tag/([^"]+)""#, entry)?;
+				let title = regex_captures!(r"(?s)<title>(.*?)</title>", entry)
+					.map_or("", |(_, title)| title);
+				let updated_at = regex_captures!(r"<updated>(.*?)</updated>", entry)
+					.map_or("", |(_, updated_at)| updated_at);
+				let notes = regex_captures!(r"(?s)<content[^>]*>(.*?)</content>", entry)
+					.map_or("", |(_, notes)| notes);
+
+				Some(ModRelease {
+					tag: tag.to_string(),
+					title: unescape_xml(title),
+					updated_at: updated_at.to_string(),
+					notes: unescape_xml(notes),
+				})
+			})
+			.collect())
+	}
+
+	// The release page loads its list of assets from this url, which is plain HTML.
+	pub async fn get_release_assets(&self, tag: &str) -> Result<Vec<String>> {
+		let html = reqwest::get(format!("{}/expanded_assets/{tag}", self.get_releases_url()))
+			.await?
+			.error_for_status()?
+			.text()
+			.await?;
+
+		let mut assets: Vec<String> = Vec::default();
+		for asset_captures in
+			regex!(r#"href="[^"]*/releases/download/[^/"]+/([^"]+)""#).captures_iter(&html)
+		{
+			if let Some(asset) = asset_captures.get(1) {
+				let asset_name = asset.as_str().to_string();
+				if !assets.contains(&asset_name) {
+					assets.push(asset_name);
+				}
+			}
+		}
+
+		Ok(assets)
 	}
 
 	fn get_releases_url(&self) -> String {
@@ -160,6 +596,14 @@ impl ModGithubInfo {
 	}
 }
 
+fn unescape_xml(text: &str) -> String {
+	text.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&#39;", "'")
+		.replace("&amp;", "&")
+}
+
 async fn request_head(url: &str) -> Result<reqwest::Response> {
 	let client = reqwest::Client::builder()
 		// Don't follow redirects. We don't need to actually download the final page,
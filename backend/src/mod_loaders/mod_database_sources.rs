@@ -0,0 +1,86 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use super::mod_database::URL_BASE;
+use crate::{
+	game_engines::unity::UnityScriptingBackend,
+	paths::app_data_path,
+	serializable_enum,
+	serializable_struct,
+	Result,
+};
+
+serializable_enum!(ModDatabaseSourceKind {
+	// Same format as rai-pal-db.
+	RaiPal,
+	// Thunderstore package index, only has BepInEx mods.
+	Thunderstore,
+});
+
+serializable_struct!(ModDatabaseSource {
+	pub name: String,
+	// For Rai Pal sources, can be an https url, a file:// url, or a path to a local folder.
+	// Either way, it should have a {mod_loader_id}.json file for each mod loader it has mods for.
+	// For Thunderstore sources, it's the package index url (https://thunderstore.io/c/{community}/api/v1/package/),
+	// the same thing on a mirror, or a path to a local copy of the index json.
+	pub url: String,
+	#[serde(default = "default_kind")]
+	pub kind: ModDatabaseSourceKind,
+	// Thunderstore packages don't say which scripting backend they're for. Mono if not set.
+	#[serde(default)]
+	pub unity_backend: Option<UnityScriptingBackend>,
+});
+
+// Sources are in order of priority.
+// If more than one source has a mod with the same id, the one from the first source is used.
+pub type List = Vec<ModDatabaseSource>;
+
+// Sources saved before there were different kinds are always Rai Pal sources.
+const fn default_kind() -> ModDatabaseSourceKind {
+	ModDatabaseSourceKind::RaiPal
+}
+
+pub fn get_default() -> List {
+	vec![ModDatabaseSource {
+		name: "Rai Pal".to_string(),
+		url: URL_BASE.to_string(),
+		kind: ModDatabaseSourceKind::RaiPal,
+		unity_backend: None,
+	}]
+}
+
+fn sources_path() -> Result<PathBuf> {
+	Ok(app_data_path()?.join("mod-database-sources.json"))
+}
+
+fn read_sources(sources_path: &Path) -> Result<List> {
+	if !sources_path.is_file() {
+		return Ok(get_default());
+	}
+
+	let json = fs::read_to_string(sources_path)?;
+	Ok(serde_json::from_str::<List>(&json)?)
+}
+
+pub fn get_all() -> List {
+	match sources_path().and_then(|path| read_sources(&path)) {
+		Ok(sources) => sources,
+		Err(err) => {
+			error!("Failed to read mod database sources: {err}");
+			get_default()
+		}
+	}
+}
+
+pub fn set(sources: &List) -> Result {
+	fs::write(sources_path()?, serde_json::to_string_pretty(sources)?)?;
+
+	Ok(())
+}
@@ -1,9 +1,6 @@
 use std::{
 	collections::HashMap,
-	fs::{
-		self,
-		File,
-	},
+	fs,
 	path::{
 		Path,
 		PathBuf,
@@ -12,8 +9,10 @@ use std::{
 
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
-use log::error;
-use zip::ZipArchive;
+use log::{
+	error,
+	warn,
+};
 
 use super::{
 	bepinex::BepInEx,
@@ -21,6 +20,11 @@ use super::{
 	runnable_loader::RunnableLoader,
 };
 use crate::{
+	archives,
+	downloads::{
+		self,
+		DownloadProgress,
+	},
 	files,
 	game_mod::CommonModData,
 	installed_game::InstalledGame,
@@ -29,8 +33,19 @@ use crate::{
 		LocalMod,
 		ModKind,
 	},
-	mod_loaders::mod_database::ModDatabase,
+	mod_loaders::{
+		mod_database::{
+			DatabaseEntry,
+			ModDownload,
+		},
+		mod_database_sources::{
+			self,
+			ModDatabaseSource,
+			ModDatabaseSourceKind,
+		},
+	},
 	mod_manifest,
+	mod_versions,
 	paths,
 	remote_mod::{
 		RemoteMod,
@@ -58,7 +73,17 @@ pub enum ModLoader {
 #[enum_dispatch(ModLoader)]
 pub trait ModLoaderActions {
 	fn install(&self, game: &InstalledGame) -> Result;
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result;
+	fn uninstall(&self, game: &InstalledGame) -> Result;
+	// Returns the paths of all the files written to install the mod.
+	async fn install_mod_inner(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+	) -> Result<Vec<PathBuf>>;
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result;
+	fn set_mod_enabled_inner(&self, game: &InstalledGame, mod_id: &str, enabled: bool) -> Result;
+	// Makes the game start with or without this mod loader, without uninstalling anything.
+	fn set_enabled(&self, game: &InstalledGame, enabled: bool) -> Result;
 	fn get_data(&self) -> &ModLoaderData;
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf>;
 	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>>;
@@ -76,20 +101,40 @@ pub trait ModLoaderActions {
 	}
 
 	async fn install_mod(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
-		self.install_mod_inner(game, local_mod).await?;
+		let installed_files = self.install_mod_inner(game, local_mod).await?;
 
 		if self.get_data().kind != ModKind::Runnable {
+			game.save_installed_files(&local_mod.common.id, &installed_files)?;
+
 			if let Some(manifest) = &local_mod.data.manifest {
 				let manifest_path = game.get_installed_mod_manifest_path(&local_mod.common.id)?;
 				fs::create_dir_all(paths::path_parent(&manifest_path)?)?;
 				let manifest_contents = serde_json::to_string_pretty(manifest)?;
 				fs::write(manifest_path, manifest_contents)?;
 			}
+
+			// Freshly installed mods are always enabled.
+			game.set_mod_enabled_state(&local_mod.common.id, true)?;
 		}
 
 		Ok(())
 	}
 
+	fn uninstall_mod(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		self.uninstall_mod_inner(game, mod_id)?;
+
+		if self.get_data().kind != ModKind::Runnable {
+			game.remove_installed_mod_data(mod_id)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_mod_enabled(&self, game: &InstalledGame, mod_id: &str, enabled: bool) -> Result {
+		self.set_mod_enabled_inner(game, mod_id, enabled)?;
+		game.set_mod_enabled_state(mod_id, enabled)
+	}
+
 	async fn get_remote_mods<F>(&self, error_handler: F) -> HashMap<String, RemoteMod>
 	where
 		F: Fn(Error) + Send,
@@ -97,12 +142,89 @@ pub trait ModLoaderActions {
 		let data = self.get_data();
 		let loader_id = &data.id;
 
-		let database = mod_database::get(loader_id).await.unwrap_or_else(|error| {
-			error_handler(error);
-			ModDatabase { mods: Vec::new() }
-		});
+		let sources = mod_database_sources::get_all();
+
+		let databases = futures::future::join_all(
+			sources
+				.iter()
+				.map(|source| async move { (source, mod_database::get(source, loader_id).await) }),
+		)
+		.await;
+
+		// Sources are in order of priority, so the first one to have a mod wins.
+		let mut database_mods: Vec<(&ModDatabaseSource, DatabaseEntry)> = Vec::new();
+		for (source, database_result) in databases {
+			match database_result {
+				Ok((database, entry_errors)) => {
+					for entry_error in entry_errors {
+						error_handler(entry_error);
+					}
+
+					for database_mod in database.mods {
+						// Third party sources can have anything in them, so every entry gets checked.
+						if let Err(error) = database_mod.validate() {
+							error_handler(error);
+							continue;
+						}
+
+						if database_mods
+							.iter()
+							.any(|(_, existing_mod)| existing_mod.id == database_mod.id)
+						{
+							warn!(
+								"Ignoring mod `{}` from database source `{}`, since a source with higher priority already has it.",
+								database_mod.id, source.name
+							);
+						} else {
+							database_mods.push((source, database_mod));
+						}
+					}
+				}
+				Err(error) => {
+					error!(
+						"Failed to get {loader_id} database from source `{}`: {error}",
+						source.name
+					);
+					error_handler(error);
+				}
+			}
+		}
+
+		let overrides_source = ModDatabaseSource {
+			name: "Local overrides".to_string(),
+			url: mod_database::get_overrides_path(loader_id)
+				.map(|path| path.to_string_lossy().to_string())
+				.unwrap_or_default(),
+			kind: ModDatabaseSourceKind::RaiPal,
+			unity_backend: None,
+		};
+
+		match mod_database::get_overrides(loader_id) {
+			Ok(Some((overrides, entry_errors))) => {
+				for entry_error in entry_errors {
+					error_handler(entry_error);
+				}
+
+				for override_mod in overrides.mods {
+					if let Err(error) = override_mod.validate() {
+						error_handler(error);
+						continue;
+					}
+
+					database_mods.retain(|(_, existing_mod)| existing_mod.id != override_mod.id);
+					database_mods.push((&overrides_source, override_mod));
+				}
+			}
+			Ok(None) => {}
+			Err(error) => {
+				error!("Failed to read {loader_id} database overrides: {error}");
+				error_handler(error);
+			}
+		}
+
+		futures::future::join_all(database_mods.iter().map(|(source, database_mod)| async {
+			let latest_version = database_mod.get_download().await;
 
-		futures::future::join_all(database.mods.iter().map(|database_mod| async {
 			(
 				database_mod.id.clone(),
 				RemoteMod {
@@ -117,7 +239,11 @@ pub trait ModLoaderActions {
 						description: database_mod.description.clone(),
 						source_code: database_mod.source_code.clone(),
 						title: database_mod.title.clone(),
-						latest_version: database_mod.get_download().await,
+						latest_version,
+						dependencies: database_mod.dependencies.clone(),
+						conflicts: database_mod.conflicts.clone(),
+						github: database_mod.github.clone(),
+						source: source.name.clone(),
 					},
 				},
 			)
@@ -127,49 +253,111 @@ pub trait ModLoaderActions {
 		.collect()
 	}
 
-	async fn download_mod(&self, remote_mod: &RemoteMod) -> Result {
-		if let Some(latest_version) = &remote_mod.data.latest_version {
-			let target_path = self.get_mod_path(&remote_mod.common)?;
-			let mod_loader_data = self.get_data();
-			let mod_id = &remote_mod.common.id;
-			let downloads_path = paths::installed_mods_path()?
-				.join(&mod_loader_data.id)
-				.join("downloads");
-
-			let response = reqwest::get(&latest_version.url).await?;
-
-			fs::create_dir_all(&downloads_path)?;
-
-			let zip_path = downloads_path.join(format!("{}.zip", mod_id));
-
-			// TODO Stream to disk instead of keeping it all in memory.
-			fs::write(&zip_path, response.bytes().await?)?;
-			let file = File::open(&zip_path)?;
+	// Where the mod files are inside the extracted archive.
+	fn get_extracted_mod_files_path(
+		&self,
+		extract_path: &Path,
+		mod_download: &ModDownload,
+	) -> Result<PathBuf> {
+		Ok(mod_download.root.as_ref().map_or_else(
+			|| extract_path.to_path_buf(),
+			|root| extract_path.join(root),
+		))
+	}
 
-			let mut zip_archive = ZipArchive::new(file)?;
+	// The download is usually the mod's latest version, but it can also be any other release.
+	async fn download_mod<F>(
+		&self,
+		remote_mod: &RemoteMod,
+		mod_download: &ModDownload,
+		progress_handler: F,
+	) -> Result
+	where
+		F: Fn(DownloadProgress) + Send + Sync,
+	{
+		let target_path = self.get_mod_path(&remote_mod.common)?;
+		let mod_loader_data = self.get_data();
+		let mod_id = &remote_mod.common.id;
+		let downloads_path = paths::installed_mods_path()?
+			.join(&mod_loader_data.id)
+			.join("downloads");
+
+		fs::create_dir_all(&downloads_path)?;
+
+		// Could be any kind of archive, the format gets detected when extracting.
+		let archive_path = downloads_path.join(format!("{mod_id}.archive"));
+
+		downloads::download_file(
+			mod_id,
+			&mod_download.url,
+			&downloads::get_partial_path(&downloads_path, mod_id, &mod_download.url),
+			&archive_path,
+			progress_handler,
+		)
+		.await?;
+
+		downloads::verify_file(
+			&archive_path,
+			mod_download.sha256.as_deref(),
+			mod_download.size,
+		)?;
+
+		// Everything gets extracted to the downloads folder first,
+		// so that nothing half-extracted ends up in the mods folder.
+		let extract_path = downloads_path.join(mod_id);
+		if extract_path.is_dir() {
+			fs::remove_dir_all(&extract_path)?;
+		}
 
-			if let Some(root) = &latest_version.root {
-				let unzip_path = downloads_path.join(mod_id);
-				zip_archive.extract(downloads_path.join(mod_id))?;
-				files::copy_dir_all(unzip_path.join(root), &target_path)?;
-			} else {
-				zip_archive.extract(&target_path)?;
+		archives::extract(&archive_path, &extract_path)?;
+
+		let mod_files_path = self.get_extracted_mod_files_path(&extract_path, mod_download)?;
+
+		// Saves the manifest so we know which version of the mod we installed.
+		fs::write(
+			local_mod::get_manifest_path(&mod_files_path),
+			serde_json::to_string_pretty(&mod_manifest::Manifest {
+				id: Some(mod_id.clone()),
+				version: mod_download.id.clone(),
+				runnable: mod_download.runnable.clone(),
+				engine: remote_mod.common.engine,
+				unity_backend: remote_mod.common.unity_backend,
+				dependencies: remote_mod.data.dependencies.clone(),
+				conflicts: remote_mod.data.conflicts.clone(),
+			})?,
+		)?;
+
+		// Every version we download is kept, so that we can go back to it later.
+		let version_path = mod_versions::save(
+			&mod_loader_data.id,
+			mod_id,
+			&mod_download.id,
+			&mod_files_path,
+		)?;
+
+		// Only the latest version goes in the mods folder. Older releases stay in the versions folder,
+		// and get installed from there.
+		let is_latest_version = remote_mod
+			.data
+			.latest_version
+			.as_ref()
+			.map_or(false, |latest_version| {
+				latest_version.is_release(&mod_download.id)
+			});
+
+		if is_latest_version {
+			if target_path.is_dir() {
+				fs::remove_dir_all(&target_path)?;
 			}
+			fs::create_dir_all(paths::path_parent(&target_path)?)?;
+			files::copy_dir_all(&version_path, &target_path)?;
+		}
 
-			// Saves the manifest so we know which version of the mod we installed.
-			fs::write(
-				local_mod::get_manifest_path(&target_path),
-				serde_json::to_string_pretty(&mod_manifest::Manifest {
-					version: latest_version.id.clone(),
-					runnable: latest_version.runnable.clone(),
-					engine: remote_mod.common.engine,
-					unity_backend: remote_mod.common.unity_backend,
-				})?,
-			)?;
-
-			return Ok(());
+		if extract_path.is_dir() {
+			fs::remove_dir_all(&extract_path)?;
 		}
-		Err(Error::ModDownloadNotAvailable(remote_mod.common.id.clone()))
+
+		Ok(())
 	}
 }
 
@@ -105,8 +105,36 @@ impl ModLoaderActions for RunnableLoader {
 		todo!()
 	}
 
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
-		let mod_folder = self.get_mod_path(&local_mod.common)?;
+	fn uninstall(&self, _game: &InstalledGame) -> Result {
+		// Runnable mods don't install anything into the game.
+		Ok(())
+	}
+
+	fn uninstall_mod_inner(&self, _game: &InstalledGame, _mod_id: &str) -> Result {
+		Ok(())
+	}
+
+	fn set_mod_enabled_inner(
+		&self,
+		_game: &InstalledGame,
+		_mod_id: &str,
+		_enabled: bool,
+	) -> Result {
+		// Runnable mods are started on demand, so there's nothing to disable.
+		Err(Error::NotImplemented)
+	}
+
+	fn set_enabled(&self, _game: &InstalledGame, _enabled: bool) -> Result {
+		Ok(())
+	}
+
+	async fn install_mod_inner(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+	) -> Result<Vec<PathBuf>> {
+		// Not using get_mod_path, since this could be an older version of the mod.
+		let mod_folder = &local_mod.data.path;
 
 		let runnable = local_mod
 			.data
@@ -126,7 +154,8 @@ impl ModLoaderActions for RunnableLoader {
 			.args(&args)
 			.spawn()?;
 
-		Ok(())
+		// Runnable mods aren't installed into the game, so there are no files to keep track of.
+		Ok(Vec::default())
 	}
 
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
@@ -0,0 +1,294 @@
+use std::{
+	collections::{
+		HashMap,
+		HashSet,
+	},
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::{
+	error,
+	warn,
+};
+use reqwest::{
+	header::{
+		ETAG,
+		IF_NONE_MATCH,
+	},
+	StatusCode,
+};
+use sha2::{
+	Digest,
+	Sha256,
+};
+
+use crate::{
+	game_engines::{
+		game_engine::GameEngineBrand,
+		unity::UnityScriptingBackend,
+	},
+	game_mod::ModRequirement,
+	mod_loaders::mod_database::{
+		DatabaseEntry,
+		ModDatabase,
+		ModDownload,
+		SCHEMA_VERSION,
+	},
+	paths,
+	Error,
+	Result,
+};
+
+// Only the fields we care about, from Thunderstore's /api/v1/package/ index.
+#[derive(serde::Deserialize)]
+struct Package {
+	name: String,
+	full_name: String,
+	owner: String,
+	package_url: String,
+	#[serde(default)]
+	is_deprecated: bool,
+	// Newest version first.
+	versions: Vec<PackageVersion>,
+}
+
+#[derive(serde::Deserialize)]
+struct PackageVersion {
+	description: String,
+	version_number: String,
+	// Each one looks like {OWNER}-{NAME}-{VERSION}.
+	dependencies: Vec<String>,
+	download_url: String,
+	file_size: Option<u64>,
+	#[serde(default)]
+	website_url: String,
+}
+
+// Rai Pal installs BepInEx by itself, so the packs that Thunderstore uses for it are skipped.
+fn is_mod_loader_pack(package_id: &str) -> bool {
+	package_id
+		.split_once('-')
+		.is_some_and(|(_, name)| name.starts_with("BepInExPack"))
+}
+
+// Thunderstore dependencies are a minimum version.
+fn parse_dependency(dependency: &str) -> Option<ModRequirement> {
+	let (id, version) = dependency.rsplit_once('-')?;
+
+	Some(ModRequirement {
+		id: id.to_string(),
+		version: Some(format!(">={version}")),
+	})
+}
+
+impl Package {
+	fn get_dependencies(&self) -> impl Iterator<Item = ModRequirement> + '_ {
+		self.versions
+			.iter()
+			.take(1)
+			.flat_map(|latest_version| &latest_version.dependencies)
+			.filter(|dependency| !is_mod_loader_pack(dependency))
+			.filter_map(|dependency| parse_dependency(dependency))
+	}
+
+	// Dependencies that aren't in the index (like packages from other communities) are left out,
+	// since we'd never be able to install them, and that would make the whole mod impossible to install.
+	fn into_database_entry(
+		self,
+		unity_backend: UnityScriptingBackend,
+		available_ids: &HashSet<String>,
+	) -> Option<DatabaseEntry> {
+		let dependencies = self
+			.get_dependencies()
+			.filter(|dependency| {
+				let is_available = available_ids.contains(&dependency.id);
+				if !is_available {
+					warn!(
+						"Skipping dependency `{}` of Thunderstore package `{}`, since it isn't in the index",
+						dependency.id, self.full_name
+					);
+				}
+				is_available
+			})
+			.collect();
+
+		let latest_version = self.versions.into_iter().next()?;
+
+		Some(DatabaseEntry {
+			id: self.full_name,
+			title: self.name.replace('_', " "),
+			author: self.owner,
+			source_code: if latest_version.website_url.is_empty() {
+				self.package_url
+			} else {
+				latest_version.website_url
+			},
+			description: latest_version.description,
+			engine: Some(GameEngineBrand::Unity),
+			unity_backend: Some(unity_backend),
+			github: None,
+			redownload_id: None,
+			dependencies,
+			conflicts: Vec::default(),
+			latest_version: Some(ModDownload {
+				id: latest_version.version_number,
+				url: latest_version.download_url,
+				// Packages can have a BepInEx folder or just loose files, so the BepInEx loader figures out the root after extracting.
+				root: None,
+				runnable: None,
+				sha256: None,
+				size: latest_version.file_size,
+			}),
+		})
+	}
+}
+
+// Deprecated packages are hidden, unless a package we show still depends on them.
+fn get_available_ids(packages: &[Package]) -> HashSet<String> {
+	let packages_by_id: HashMap<&str, &Package> = packages
+		.iter()
+		.map(|package| (package.full_name.as_str(), package))
+		.collect();
+
+	let mut available_ids = HashSet::new();
+	let mut pending_packages: Vec<&Package> = packages
+		.iter()
+		.filter(|package| !package.is_deprecated)
+		.collect();
+
+	while let Some(package) = pending_packages.pop() {
+		if is_mod_loader_pack(&package.full_name)
+			|| !available_ids.insert(package.full_name.clone())
+		{
+			continue;
+		}
+
+		pending_packages.extend(
+			package
+				.get_dependencies()
+				.filter_map(|dependency| packages_by_id.get(dependency.id.as_str()).copied()),
+		);
+	}
+
+	available_ids
+}
+
+// Thunderstore packages don't say which scripting backend they're for, so that comes from the source settings.
+pub fn parse_index(
+	index_name: &str,
+	bytes: &[u8],
+	unity_backend: UnityScriptingBackend,
+) -> Result<(ModDatabase, Vec<Error>)> {
+	let raw_packages = serde_json::from_slice::<Vec<serde_json::Value>>(bytes)?;
+
+	let mut packages = Vec::new();
+	let mut errors = Vec::new();
+
+	for (index, raw_package) in raw_packages.into_iter().enumerate() {
+		let package_id = raw_package
+			.get("full_name")
+			.and_then(serde_json::Value::as_str)
+			.unwrap_or("unknown")
+			.to_string();
+
+		match serde_path_to_error::deserialize::<_, Package>(raw_package) {
+			Ok(package) => packages.push(package),
+			Err(error) => {
+				errors.push(Error::InvalidModDatabaseEntry(
+					package_id,
+					format!(
+						"at `[{index}].{}` in `{index_name}`: {}",
+						error.path(),
+						error.inner()
+					),
+				));
+			}
+		}
+	}
+
+	let available_ids = get_available_ids(&packages);
+	let mods = packages
+		.into_iter()
+		.filter(|package| available_ids.contains(&package.full_name))
+		.filter_map(|package| package.into_database_entry(unity_backend, &available_ids))
+		.collect();
+
+	Ok((
+		ModDatabase {
+			schema_version: SCHEMA_VERSION,
+			mods,
+		},
+		errors,
+	))
+}
+
+fn get_cache_path(url: &str) -> Result<PathBuf> {
+	let hash = Sha256::digest(url.as_bytes());
+
+	Ok(paths::app_data_path()?
+		.join("thunderstore-cache")
+		.join(format!("{hash:x}.json")))
+}
+
+fn save_cache(cache_path: &Path, bytes: &[u8], etag: Option<&str>) -> Result {
+	fs::create_dir_all(paths::path_parent(cache_path)?)?;
+	fs::write(cache_path, bytes)?;
+
+	let etag_path = cache_path.with_extension("etag");
+	if let Some(etag) = etag {
+		fs::write(etag_path, etag)?;
+	} else if etag_path.is_file() {
+		fs::remove_file(etag_path)?;
+	}
+
+	Ok(())
+}
+
+// The index for a big community is tens of megabytes, so we keep a copy and only download it again when it changes.
+// The copy is also used if Thunderstore can't be reached.
+pub async fn get_index_bytes(url: &str) -> Result<Vec<u8>> {
+	let cache_path = get_cache_path(url)?;
+	let cached_etag = cache_path
+		.is_file()
+		.then(|| fs::read_to_string(cache_path.with_extension("etag")).ok())
+		.flatten();
+
+	let mut request = reqwest::Client::new().get(url);
+	if let Some(etag) = &cached_etag {
+		request = request.header(IF_NONE_MATCH, etag.as_str());
+	}
+
+	let response = match request
+		.send()
+		.await
+		.and_then(reqwest::Response::error_for_status)
+	{
+		Ok(response) => response,
+		Err(err) if cache_path.is_file() => {
+			warn!("Failed to download Thunderstore index `{url}`, using the cached copy. Error: {err}");
+			return Ok(fs::read(cache_path)?);
+		}
+		Err(err) => return Err(err.into()),
+	};
+
+	if response.status() == StatusCode::NOT_MODIFIED {
+		return Ok(fs::read(cache_path)?);
+	}
+
+	let etag = response
+		.headers()
+		.get(ETAG)
+		.and_then(|etag| etag.to_str().ok())
+		.map(str::to_string);
+	let bytes = response.bytes().await?.to_vec();
+
+	if let Err(err) = save_cache(&cache_path, &bytes, etag.as_deref()) {
+		error!("Failed to save Thunderstore index cache for `{url}`. Error: {err}");
+	}
+
+	Ok(bytes)
+}
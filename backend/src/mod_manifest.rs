@@ -10,15 +10,23 @@ use crate::{
 		game_engine::GameEngineBrand,
 		unity::UnityScriptingBackend,
 	},
+	game_mod::ModRequirement,
 	mod_loaders::mod_database::RunnableModData,
 	serializable_struct,
 };
 
 serializable_struct!(Manifest {
+	// Manifests from before we saved the id don't have it.
+	#[serde(default)]
+	pub id: Option<String>,
 	pub version: String,
 	pub runnable: Option<RunnableModData>,
 	pub engine: Option<GameEngineBrand>,
 	pub unity_backend: Option<UnityScriptingBackend>,
+	#[serde(default)]
+	pub dependencies: Vec<ModRequirement>,
+	#[serde(default)]
+	pub conflicts: Vec<ModRequirement>,
 });
 
 impl Manifest {
@@ -0,0 +1,110 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::PathBuf,
+};
+
+use crate::{
+	installed_game::InstalledGame,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+serializable_struct!(ModProfile {
+	pub name: String,
+	// Keys are mod ids, values are the versions that were installed when the profile was saved.
+	pub mods: HashMap<String, String>,
+	pub launch_vanilla: bool,
+});
+
+// What changed in the game after applying a profile.
+serializable_struct!(ModProfileChanges {
+	pub installed: Vec<String>,
+	pub enabled: Vec<String>,
+	pub disabled: Vec<String>,
+	// Mods in the profile that we couldn't find anywhere, locally or remotely.
+	pub unavailable: Vec<String>,
+	// Mods that got installed, but with a different version than the one in the profile.
+	pub version_mismatches: Vec<String>,
+});
+
+fn get_profiles_path(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(game.get_installed_mods_folder()?.join("profiles.json"))
+}
+
+pub fn get_all(game: &InstalledGame) -> Result<Vec<ModProfile>> {
+	let profiles_path = get_profiles_path(game)?;
+	if !profiles_path.is_file() {
+		return Ok(Vec::default());
+	}
+
+	Ok(serde_json::from_str(&fs::read_to_string(profiles_path)?)?)
+}
+
+pub fn get(game: &InstalledGame, name: &str) -> Result<ModProfile> {
+	get_all(game)?
+		.into_iter()
+		.find(|profile| profile.name == name)
+		.ok_or_else(|| Error::ModProfileNotFound(name.to_string()))
+}
+
+fn write_all(game: &InstalledGame, profiles: &[ModProfile]) -> Result {
+	fs::write(
+		get_profiles_path(game)?,
+		serde_json::to_string_pretty(profiles)?,
+	)?;
+
+	Ok(())
+}
+
+// New profiles are a snapshot of the mods that are currently installed and enabled.
+pub fn create(game: &InstalledGame, name: &str) -> Result<ModProfile> {
+	let mut profiles = get_all(game)?;
+	if profiles.iter().any(|profile| profile.name == name) {
+		return Err(Error::ModProfileAlreadyExists(name.to_string()));
+	}
+
+	let profile = ModProfile {
+		name: name.to_string(),
+		mods: game
+			.installed_mod_versions
+			.iter()
+			.filter(|(_, installed_mod)| installed_mod.enabled)
+			.map(|(mod_id, installed_mod)| (mod_id.clone(), installed_mod.version.clone()))
+			.collect(),
+		launch_vanilla: game.launch_vanilla,
+	};
+
+	profiles.push(profile.clone());
+	write_all(game, &profiles)?;
+
+	Ok(profile)
+}
+
+pub fn rename(game: &InstalledGame, name: &str, new_name: &str) -> Result {
+	let mut profiles = get_all(game)?;
+	if profiles.iter().any(|profile| profile.name == new_name) {
+		return Err(Error::ModProfileAlreadyExists(new_name.to_string()));
+	}
+
+	profiles
+		.iter_mut()
+		.find(|profile| profile.name == name)
+		.ok_or_else(|| Error::ModProfileNotFound(name.to_string()))?
+		.name = new_name.to_string();
+
+	write_all(game, &profiles)
+}
+
+pub fn delete(game: &InstalledGame, name: &str) -> Result {
+	let mut profiles = get_all(game)?;
+	let profile_count = profiles.len();
+	profiles.retain(|profile| profile.name != name);
+
+	if profiles.len() == profile_count {
+		return Err(Error::ModProfileNotFound(name.to_string()));
+	}
+
+	write_all(game, &profiles)
+}
@@ -0,0 +1,72 @@
+use crate::{
+	installed_game,
+	local_mod,
+	remote_mod,
+	serializable_struct,
+};
+
+serializable_struct!(ModUpdate {
+	pub game_id: String,
+	pub mod_id: String,
+	pub installed_version: String,
+	pub latest_version: String,
+	// True if the local copy of the mod is also outdated, so it needs to be downloaded again.
+	pub needs_download: bool,
+});
+
+serializable_struct!(ModUpdateFailure {
+	pub game_id: String,
+	pub mod_id: String,
+	pub error: String,
+});
+
+serializable_struct!(ModUpdateSummary {
+	pub updated: Vec<ModUpdate>,
+	pub failed: Vec<ModUpdateFailure>,
+});
+
+// The latest version is the remote one if we know it, otherwise it's whatever we have locally.
+// Pinned and disabled mods are left alone, since installing them again would undo that.
+pub fn get_all(
+	installed_games: &installed_game::Map,
+	local_mods: &local_mod::Map,
+	remote_mods: &remote_mod::Map,
+) -> Vec<ModUpdate> {
+	let mut updates: Vec<_> = installed_games
+		.values()
+		.flat_map(|game| {
+			game.installed_mod_versions
+				.iter()
+				.filter(|(_, installed_mod)| {
+					installed_mod.enabled && installed_mod.pinned_version.is_none()
+				})
+				.filter_map(|(mod_id, installed_mod)| {
+					let local_version = local_mods
+						.get(mod_id)
+						.and_then(|local_mod| local_mod.data.manifest.as_ref())
+						.map(|manifest| manifest.version.clone());
+
+					let remote_version = remote_mods
+						.get(mod_id)
+						.and_then(|remote_mod| remote_mod.data.latest_version.as_ref())
+						.map(|latest_version| latest_version.id.clone());
+
+					let latest_version =
+						remote_version.clone().or_else(|| local_version.clone())?;
+
+					(latest_version != installed_mod.version).then(|| ModUpdate {
+						game_id: game.id.clone(),
+						mod_id: mod_id.clone(),
+						installed_version: installed_mod.version.clone(),
+						needs_download: remote_version.is_some() && local_version != remote_version,
+						latest_version,
+					})
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect();
+
+	updates.sort_by(|a, b| (&a.game_id, &a.mod_id).cmp(&(&b.game_id, &b.mod_id)));
+
+	updates
+}
@@ -0,0 +1,131 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+	time::SystemTime,
+};
+
+use log::error;
+
+use crate::{
+	files,
+	local_mod,
+	mod_manifest,
+	paths,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// How many downloaded versions of each mod we keep around, not counting the ones games are using.
+const KEPT_VERSION_COUNT: usize = 3;
+
+serializable_struct!(ModVersion {
+	pub version: String,
+	pub path: PathBuf,
+});
+
+fn get_versions_folder(loader_id: &str, mod_id: &str) -> Result<PathBuf> {
+	Ok(paths::installed_mods_path()?
+		.join(loader_id)
+		.join("versions")
+		.join(mod_id))
+}
+
+// Version ids come from the database, so they could have characters that aren't valid in folder names.
+fn get_version_folder_name(version: &str) -> String {
+	version
+		.chars()
+		.map(|character| {
+			if character.is_ascii_alphanumeric() || ['.', '-', '_'].contains(&character) {
+				character
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+fn get_downloaded_time(version_path: &Path) -> SystemTime {
+	fs::metadata(local_mod::get_manifest_path(version_path))
+		.and_then(|metadata| metadata.modified())
+		.unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+// Newest versions first.
+pub fn get_all(loader_id: &str, mod_id: &str) -> Vec<ModVersion> {
+	let versions_folder = match get_versions_folder(loader_id, mod_id) {
+		Ok(versions_folder) if versions_folder.is_dir() => versions_folder,
+		Ok(_) => return Vec::default(),
+		Err(err) => {
+			error!("Failed to get versions folder for mod {mod_id}: {err}");
+			return Vec::default();
+		}
+	};
+
+	let mut versions: Vec<_> = fs::read_dir(versions_folder)
+		.map(|entries| {
+			entries
+				.filter_map(|entry| {
+					let path = entry.ok()?.path();
+					let manifest = mod_manifest::get(&local_mod::get_manifest_path(&path))?;
+					Some((
+						get_downloaded_time(&path),
+						ModVersion {
+							version: manifest.version,
+							path,
+						},
+					))
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+
+	versions.sort_by_key(|(downloaded_time, _)| std::cmp::Reverse(*downloaded_time));
+
+	versions.into_iter().map(|(_, version)| version).collect()
+}
+
+pub fn get(loader_id: &str, mod_id: &str, version: &str) -> Result<ModVersion> {
+	get_all(loader_id, mod_id)
+		.into_iter()
+		.find(|mod_version| mod_version.version == version)
+		.ok_or_else(|| Error::ModVersionNotFound(mod_id.to_string(), version.to_string()))
+}
+
+// Moves the downloaded mod files to the versions folder, replacing any previous download of the same version.
+pub fn save(
+	loader_id: &str,
+	mod_id: &str,
+	version: &str,
+	mod_files_path: &Path,
+) -> Result<PathBuf> {
+	let version_path =
+		get_versions_folder(loader_id, mod_id)?.join(get_version_folder_name(version));
+
+	if version_path.is_dir() {
+		fs::remove_dir_all(&version_path)?;
+	}
+	fs::create_dir_all(paths::path_parent(&version_path)?)?;
+
+	if fs::rename(mod_files_path, &version_path).is_err() {
+		files::copy_dir_all(mod_files_path, &version_path)?;
+	}
+
+	Ok(version_path)
+}
+
+// Deletes old versions, except for the newest few and any version in `kept_versions`.
+pub fn prune(loader_id: &str, mod_id: &str, kept_versions: &[String]) -> Result {
+	for mod_version in get_all(loader_id, mod_id)
+		.into_iter()
+		.skip(KEPT_VERSION_COUNT)
+		.filter(|mod_version| !kept_versions.contains(&mod_version.version))
+	{
+		fs::remove_dir_all(&mod_version.path)?;
+	}
+
+	Ok(())
+}
@@ -6,6 +6,7 @@ use std::{
 		Hasher,
 	},
 	path::{
+		Component,
 		Path,
 		PathBuf,
 	},
@@ -14,6 +15,10 @@ use std::{
 use directories::ProjectDirs;
 use glob::glob;
 use log::error;
+use sha2::{
+	Digest,
+	Sha256,
+};
 
 use crate::{
 	Error,
@@ -81,6 +86,17 @@ pub fn file_name_without_extension(file_path: &Path) -> Result<&str> {
 		.ok_or_else(|| Error::PathParseFailure(file_path.to_path_buf()))
 }
 
+// For names that end up as a single folder or file name, like mod ids.
+// Anything with separators, `..`, or a drive could point somewhere else.
+pub fn is_single_component(name: &str) -> bool {
+	let mut components = Path::new(name).components();
+
+	match (components.next(), components.next()) {
+		(Some(Component::Normal(component)), None) => component == name,
+		_ => false,
+	}
+}
+
 pub fn normalize_path(path: &Path) -> PathBuf {
 	path.canonicalize().unwrap_or_else(|err| {
 		error!(
@@ -92,7 +108,27 @@ pub fn normalize_path(path: &Path) -> PathBuf {
 	})
 }
 
+// Bump this if the way we hash paths ever changes,
+// so that ids from different versions can't be mixed up.
+const PATH_HASH_VERSION: u32 = 2;
+
+// Used for game ids, which are also used for folder names, so these need to stay the same forever.
+// Paths are case insensitive on Windows, and can use either kind of slash.
 pub fn hash_path(path: &Path) -> String {
+	let mut path_string = path.to_string_lossy().replace('\\', "/");
+	if cfg!(windows) {
+		path_string = path_string.to_lowercase();
+	}
+
+	let hash = Sha256::digest(path_string.as_bytes());
+	let hash_string = format!("{hash:x}");
+
+	format!("v{PATH_HASH_VERSION}-{}", &hash_string[..32])
+}
+
+// This is how we used to hash paths. DefaultHasher isn't guaranteed to stay the same between Rust versions,
+// so this is only here to find data stored with the old ids and move it to the new ones.
+pub fn legacy_hash_path(path: &Path) -> String {
 	let mut hasher = DefaultHasher::new();
 	path.hash(&mut hasher);
 	hasher.finish().to_string()
@@ -0,0 +1,238 @@
+use std::{
+	collections::HashSet,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use async_trait::async_trait;
+use log::error;
+
+use super::{
+	provider::{
+		ProviderActions,
+		ProviderId,
+		ProviderStatic,
+	},
+	provider_settings::ProviderSettings,
+};
+use crate::{
+	installed_game::InstalledGame,
+	owned_game::OwnedGame,
+	paths::file_name_without_extension,
+	remote_game::RemoteGame,
+	Result,
+};
+
+const DEFAULT_SCAN_DEPTH: u32 = 3;
+
+// Folders that never contain the game we're looking for.
+// Unreal games have an Engine folder with exes that look a lot like game exes,
+// but the game itself is always found before we get there.
+const IGNORED_FOLDER_NAMES: [&str; 9] = [
+	"engine",
+	"_commonredist",
+	"commonredist",
+	"redist",
+	"redistributables",
+	"directx",
+	"__installer",
+	"crashreporter",
+	"monobleedingedge",
+];
+
+// Folders that executables are often inside of, but that don't say which game it is.
+const GENERIC_FOLDER_NAMES: [&str; 10] = [
+	"binaries", "win64", "win32", "wingdk", "windows", "linux", "bin", "x64", "x86", "game",
+];
+
+// Exes that ship next to the game exe, but aren't the game.
+const IGNORED_EXECUTABLE_PREFIXES: [&str; 9] = [
+	"unitycrashhandler",
+	"crashreportclient",
+	"crashpad_handler",
+	"vc_redist",
+	"vcredist",
+	"dxsetup",
+	"dotnetfx",
+	"unins",
+	"uninstall",
+];
+
+#[derive(Clone)]
+pub struct Directory {
+	root_paths: Vec<PathBuf>,
+	scan_depth: u32,
+}
+
+impl ProviderStatic for Directory {
+	const ID: &'static ProviderId = &ProviderId::Directory;
+
+	fn new(settings: &ProviderSettings) -> Result<Self>
+	where
+		Self: Sized,
+	{
+		Ok(Self {
+			root_paths: settings.root_paths.clone(),
+			scan_depth: settings.scan_depth.unwrap_or(DEFAULT_SCAN_DEPTH),
+		})
+	}
+}
+
+#[async_trait]
+impl ProviderActions for Directory {
+	fn get_installed_games(&self) -> Result<Vec<InstalledGame>> {
+		let mut games = Vec::default();
+		let mut used_paths = HashSet::default();
+
+		for root_path in &self.root_paths {
+			if let Err(err) = scan_folder(
+				root_path,
+				root_path,
+				self.scan_depth,
+				&mut games,
+				&mut used_paths,
+			) {
+				error!(
+					"Failed to scan library folder `{}`: {err}",
+					root_path.display()
+				);
+			}
+		}
+
+		Ok(games)
+	}
+
+	fn get_owned_games(&self) -> Result<Vec<OwnedGame>> {
+		Ok(Vec::default())
+	}
+
+	async fn get_remote_games(&self) -> Result<Vec<RemoteGame>> {
+		Ok(Vec::default())
+	}
+}
+
+fn get_lowercase_file_name(path: &Path) -> Option<String> {
+	Some(path.file_name()?.to_str()?.to_lowercase())
+}
+
+pub fn is_ignored_folder(path: &Path) -> bool {
+	get_lowercase_file_name(path).map_or(true, |folder_name| {
+		folder_name.starts_with('.') || IGNORED_FOLDER_NAMES.contains(&folder_name.as_str())
+	})
+}
+
+pub fn is_ignored_executable(path: &Path) -> bool {
+	get_lowercase_file_name(path).map_or(true, |file_name| {
+		IGNORED_EXECUTABLE_PREFIXES
+			.iter()
+			.any(|prefix| file_name.starts_with(prefix))
+	})
+}
+
+// Games are named after their folder, since the exe names are often something generic like "Game.exe".
+// Folders like Binaries/Win64 (Unreal) are skipped, so the name comes from the game folder above them.
+fn get_game_name(root_path: &Path, executable_path: &Path) -> Option<String> {
+	let game_folder = executable_path
+		.ancestors()
+		.skip(1)
+		.take_while(|folder| *folder != root_path && folder.starts_with(root_path))
+		.find(|folder| {
+			get_lowercase_file_name(folder).map_or(false, |folder_name| {
+				!GENERIC_FOLDER_NAMES.contains(&folder_name.as_str())
+			})
+		});
+
+	match game_folder {
+		Some(game_folder) => Some(game_folder.file_name()?.to_str()?.to_string()),
+		None => Some(
+			file_name_without_extension(executable_path)
+				.ok()?
+				.to_string(),
+		),
+	}
+}
+
+// The path relative to the library root doesn't change if the whole library is moved,
+// so it works as a stable id for the game within this provider.
+fn get_provider_game_id(root_path: &Path, executable_path: &Path) -> Option<String> {
+	let relative_path = executable_path.strip_prefix(root_path).ok()?;
+
+	Some(
+		relative_path
+			.components()
+			.filter_map(|component| component.as_os_str().to_str())
+			.collect::<Vec<_>>()
+			.join("/"),
+	)
+}
+
+fn create_game(root_path: &Path, executable_path: &Path) -> Option<InstalledGame> {
+	let name = get_game_name(root_path, executable_path)?;
+	let mut game = InstalledGame::new(executable_path, &name, *Directory::ID)?;
+
+	game.set_provider_game_id(&get_provider_game_id(root_path, executable_path)?);
+
+	Some(game)
+}
+
+fn scan_folder(
+	root_path: &Path,
+	folder_path: &Path,
+	remaining_depth: u32,
+	games: &mut Vec<InstalledGame>,
+	used_paths: &mut HashSet<PathBuf>,
+) -> Result {
+	let mut entries: Vec<PathBuf> = fs::read_dir(folder_path)?
+		.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+		.collect();
+
+	// Sorting makes the results the same regardless of the order the file system gives us.
+	entries.sort();
+
+	let mut found_game = false;
+
+	for file_path in entries.iter().filter(|path| path.is_file()) {
+		if is_ignored_executable(file_path) {
+			continue;
+		}
+
+		if let Some(game) = create_game(root_path, file_path) {
+			found_game = true;
+
+			// Different launch exes can resolve to the same game exe (Unreal launchers, for instance).
+			if used_paths.insert(game.executable.path.clone()) {
+				games.push(game);
+			}
+		}
+	}
+
+	// Once we find a game, we presume the sub folders are part of that game,
+	// otherwise we'd find things like the Unreal shipping exe a second time.
+	if found_game || remaining_depth == 0 {
+		return Ok(());
+	}
+
+	for sub_folder_path in entries.iter().filter(|path| path.is_dir()) {
+		if is_ignored_folder(sub_folder_path) {
+			continue;
+		}
+
+		if let Err(err) = scan_folder(
+			root_path,
+			sub_folder_path,
+			remaining_depth - 1,
+			games,
+			used_paths,
+		) {
+			error!(
+				"Failed to scan folder `{}`: {err}",
+				sub_folder_path.display()
+			);
+		}
+	}
+
+	Ok(())
+}
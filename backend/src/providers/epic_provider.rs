@@ -1,17 +1,7 @@
-#![cfg(target_os = "windows")]
-
-use std::{
-	fs::{
-		self,
-		File,
-	},
-	io::Read,
-	path::PathBuf,
-};
+use std::path::PathBuf;
 
 use async_trait::async_trait;
-use base64::engine::general_purpose;
-use log::error;
+#[cfg(target_os = "windows")]
 use winreg::{
 	enums::HKEY_LOCAL_MACHINE,
 	RegKey,
@@ -23,11 +13,18 @@ use super::{
 		ProviderCommand,
 		ProviderCommandAction,
 	},
+	provider_settings::ProviderSettings,
 };
 use crate::{
+	epic::{
+		catalog::{
+			self,
+			EpicCatalogItem,
+		},
+		manifest,
+	},
 	installed_game::InstalledGame,
 	owned_game::OwnedGame,
-	paths::glob_path,
 	pc_gaming_wiki,
 	provider::{
 		ProviderActions,
@@ -37,7 +34,6 @@ use crate::{
 		self,
 		RemoteGame,
 	},
-	serializable_struct,
 	Result,
 };
 
@@ -51,24 +47,18 @@ pub struct Epic {
 impl ProviderStatic for Epic {
 	const ID: &'static ProviderId = &ProviderId::Epic;
 
-	fn new() -> Result<Self>
+	fn new(settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized,
 	{
-		let app_data_path = RegKey::predef(HKEY_LOCAL_MACHINE)
-			.open_subkey(r"SOFTWARE\WOW6432Node\Epic Games\EpicGamesLauncher")
-			.and_then(|launcher_reg| launcher_reg.get_value::<String, _>("AppDataPath"))
-			.map(PathBuf::from)?;
+		let app_data_path = match settings.root_path() {
+			Some(root_path) => root_path.to_path_buf(),
+			None => find_app_data_path()?,
+		};
 
 		let remote_game_cache = Self::try_get_remote_game_cache();
 
-		let mut file = File::open(app_data_path.join("Catalog").join("catcache.bin"))?;
-
-		let mut decoder = base64::read::DecoderReader::new(&mut file, &general_purpose::STANDARD);
-		let mut json = String::default();
-		decoder.read_to_string(&mut json)?;
-
-		let catalog = serde_json::from_str::<Vec<EpicCatalogItem>>(&json)?;
+		let catalog = catalog::read(&app_data_path)?;
 
 		Ok(Self {
 			app_data_path,
@@ -78,107 +68,31 @@ impl ProviderStatic for Epic {
 	}
 }
 
-serializable_struct!(EpicManifest {
-	#[serde(rename = "DisplayName")]
-	display_name: String,
-	#[serde(rename = "LaunchExecutable")]
-	launch_executable: String,
-	#[serde(rename = "InstallLocation")]
-	install_location: String,
-	#[serde(rename = "CatalogNamespace")]
-	catalog_namespace: String,
-	#[serde(rename = "CatalogItemId")]
-	catalog_item_id: String,
-	#[serde(rename = "AppName")]
-	app_name: String,
-});
-
-serializable_struct!(EpicCatalogCategory { path: String });
-
-serializable_struct!(EpicCatalogReleaseInfo {
-	app_id: String,
-	platform: Vec<String>,
-	date_added: Option<String>,
-});
-
-serializable_struct!(EpicCatalogImage {
-	height: i32,
-	url: String,
-});
-
-serializable_struct!(EpicCatalogItem {
-	id: String,
-	namespace: String,
-	title: String,
-	categories: Vec<EpicCatalogCategory>,
-	release_info: Vec<EpicCatalogReleaseInfo>,
-	key_images: Vec<EpicCatalogImage>,
-});
-
-impl EpicCatalogItem {
-	fn get_release_date(&self) -> Option<i64> {
-		Some(
-			self.release_info
-				.first()?
-				.date_added
-				.as_ref()?
-				.parse::<chrono::DateTime<chrono::Utc>>()
-				.ok()?
-				.timestamp(),
-		)
-	}
-
-	fn get_thumbnail_url(&self) -> Option<String> {
-		Some(
-			self.key_images
-				.iter()
-				// The available images here seem to be a bit inconsistent, so we'll just pick the smallest one.
-				.min_by_key(|image| image.height)?
-				.url
-				.clone(),
-		)
-	}
-}
-
 #[async_trait]
 impl ProviderActions for Epic {
 	fn get_installed_games(&self) -> Result<Vec<InstalledGame>> {
-		let manifests = glob_path(&self.app_data_path.join("Manifests").join("*.item"));
-
-		Ok(manifests
+		Ok(manifest::read_all(&self.app_data_path)
 			.iter()
-			.filter_map(
-				|manifest_path_result| match read_manifest(manifest_path_result) {
-					Ok(manifest) => {
-						let path = PathBuf::from(manifest.install_location)
-							.join(manifest.launch_executable);
-
-						let mut game =
-							InstalledGame::new(&path, &manifest.display_name, Self::ID.to_owned())?;
-						game.set_start_command_string(&format!(
-							"com.epicgames.launcher://apps/{}?action=launch&silent=true",
-							manifest.app_name
-						));
-						game.set_provider_game_id(&manifest.catalog_item_id);
-
-						Some(game)
-					}
-					Err(err) => {
-						error!("Failed to parse manifest: {err}");
-						None
-					}
-				},
-			)
+			.filter_map(|manifest| {
+				let mut game = InstalledGame::new(
+					&manifest.get_executable_path(),
+					&manifest.display_name,
+					Self::ID.to_owned(),
+				)?;
+				game.set_start_command_string(&format!(
+					"com.epicgames.launcher://apps/{}?action=launch&silent=true",
+					manifest.app_name
+				));
+				game.set_provider_game_id(&manifest.catalog_item_id);
+
+				Some(game)
+			})
 			.collect())
 	}
 
 	fn get_owned_games(&self) -> Result<Vec<OwnedGame>> {
 		let owned_games = self.catalog.iter().filter_map(|catalog_item| {
-			if catalog_item
-				.categories
-				.iter()
-				.all(|category| category.path != "games")
-			{
+			if !catalog_item.is_game() {
 				return None;
 			}
 
@@ -252,8 +166,15 @@ impl ProviderActions for Epic {
 	}
 }
 
-fn read_manifest(path: &PathBuf) -> Result<EpicManifest> {
-	let json = fs::read_to_string(path)?;
-	let manifest = serde_json::from_str::<EpicManifest>(&json)?;
-	Ok(manifest)
+#[cfg(target_os = "windows")]
+fn find_app_data_path() -> Result<PathBuf> {
+	Ok(RegKey::predef(HKEY_LOCAL_MACHINE)
+		.open_subkey(r"SOFTWARE\WOW6432Node\Epic Games\EpicGamesLauncher")
+		.and_then(|launcher_reg| launcher_reg.get_value::<String, _>("AppDataPath"))
+		.map(PathBuf::from)?)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn find_app_data_path() -> Result<PathBuf> {
+	Err(crate::Error::ProviderDataNotFound(Epic::ID.to_string()))
 }
@@ -11,9 +11,12 @@ use rusqlite::{
 	OpenFlags,
 };
 
-use super::provider_command::{
-	ProviderCommand,
-	ProviderCommandAction,
+use super::{
+	provider_command::{
+		ProviderCommand,
+		ProviderCommandAction,
+	},
+	provider_settings::ProviderSettings,
 };
 use crate::{
 	installed_game::InstalledGame,
@@ -42,14 +45,17 @@ pub struct Itch {
 impl ProviderStatic for Itch {
 	const ID: &'static ProviderId = &ProviderId::Itch;
 
-	fn new() -> Result<Self>
+	fn new(settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized,
 	{
-		let app_data_path = directories::BaseDirs::new()
-			.ok_or_else(Error::AppDataNotFound)?
-			.config_dir()
-			.join("itch");
+		let app_data_path = match settings.root_path() {
+			Some(root_path) => root_path.to_path_buf(),
+			None => directories::BaseDirs::new()
+				.ok_or_else(Error::AppDataNotFound)?
+				.config_dir()
+				.join("itch"),
+		};
 
 		Ok(Self {
 			database: get_database(&app_data_path)?,
@@ -1,4 +1,6 @@
 use std::{
+	cmp::Reverse,
+	collections::HashMap,
 	fs,
 	path::{
 		Path,
@@ -9,15 +11,32 @@ use std::{
 use async_trait::async_trait;
 use log::error;
 
-use super::provider::{
-	ProviderActions,
-	ProviderId,
-	ProviderStatic,
+use super::{
+	directory_provider::{
+		is_ignored_executable,
+		is_ignored_folder,
+	},
+	provider::{
+		ProviderActions,
+		ProviderId,
+		ProviderStatic,
+	},
+	provider_settings::ProviderSettings,
 };
 use crate::{
-	installed_game::InstalledGame,
+	game_engines::{
+		unity,
+		unreal,
+	},
+	game_executable::is_windows_gui_binary,
+	game_overrides::GameOverrides,
+	installed_game::{
+		self,
+		InstalledGame,
+	},
 	owned_game::OwnedGame,
 	paths::{
+		self,
 		app_data_path,
 		file_name_without_extension,
 	},
@@ -29,15 +48,29 @@ use crate::{
 
 serializable_struct!(Manual {});
 
+// How many folder levels to look into when a game is added by folder.
+const CANDIDATE_SEARCH_DEPTH: u32 = 3;
+
+pub struct FoundGame {
+	pub game: InstalledGame,
+	pub path: PathBuf,
+	// Other executables that also look like games, in case we picked the wrong one.
+	pub other_candidates: Vec<PathBuf>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct GamesConfig {
 	pub paths: Vec<PathBuf>,
+
+	// Keys are the game executable paths.
+	#[serde(default)]
+	pub overrides: HashMap<PathBuf, GameOverrides>,
 }
 
 impl ProviderStatic for Manual {
 	const ID: &'static ProviderId = &ProviderId::Manual;
 
-	fn new() -> Result<Self>
+	fn new(_settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized,
 	{
@@ -48,10 +81,20 @@ impl ProviderStatic for Manual {
 #[async_trait]
 impl ProviderActions for Manual {
 	fn get_installed_games(&self) -> Result<Vec<InstalledGame>> {
-		Ok(read_games_config(&games_config_path()?)
+		let games_config = read_games_config(&games_config_path()?);
+
+		Ok(games_config
 			.paths
 			.iter()
-			.filter_map(|path| create_game_from_path(path))
+			.filter_map(|path| {
+				let mut game = create_game_from_path(path)?;
+
+				if let Some(overrides) = games_config.overrides.get(&game.executable.path) {
+					game.set_overrides(overrides);
+				}
+
+				Some(game)
+			})
 			.collect())
 	}
 
@@ -81,15 +124,124 @@ fn read_games_config(games_config_path: &Path) -> GamesConfig {
 			error!("Error reading config: {error}");
 			GamesConfig {
 				paths: Vec::default(),
+				overrides: HashMap::default(),
+			}
+		}
+	}
+}
+
+fn has_valid_extension(path: &Path) -> bool {
+	path.extension()
+		.and_then(|extension| extension.to_str())
+		.is_some_and(|extension| installed_game::VALID_EXTENSIONS.contains(&extension))
+}
+
+fn find_candidates(folder_path: &Path, remaining_depth: u32, candidates: &mut Vec<PathBuf>) {
+	let entries = match fs::read_dir(folder_path) {
+		Ok(entries) => entries,
+		Err(err) => {
+			error!(
+				"Failed to read folder `{}` while looking for game executables: {err}",
+				folder_path.display()
+			);
+			return;
+		}
+	};
+
+	for path in entries.flatten().map(|entry| entry.path()) {
+		if path.is_dir() {
+			if remaining_depth > 0 && !is_ignored_folder(&path) {
+				find_candidates(&path, remaining_depth - 1, candidates);
 			}
+		} else if has_valid_extension(&path) && !is_ignored_executable(&path) {
+			candidates.push(path);
 		}
 	}
 }
 
-pub fn add_game(path: &Path) -> Result<InstalledGame> {
-	let game =
-		create_game_from_path(path).ok_or(Error::FailedToGetGameFromPath(path.to_path_buf()))?;
+// Higher score means the exe is more likely to be the actual game.
+fn get_candidate_score(path: &Path) -> i32 {
+	let mut score = 0;
+
+	// Unity exes have a *_Data folder next to them with the same name.
+	if unity::is_unity_exe(path) {
+		score += 4;
+	}
+
+	// Unreal games often have a launcher at the root, but the *-Shipping.exe is the real game.
+	if unreal::is_shipping_exe(path) {
+		score += 3;
+	}
+
+	// Console exes are usually tools or dedicated servers.
+	// This fails for non-PE binaries, which we just don't score.
+	match is_windows_gui_binary(path) {
+		Ok(true) => score += 1,
+		Ok(false) => score -= 2,
+		Err(_) => {}
+	}
+
+	score
+}
+
+fn rank_candidates(candidates: &mut [PathBuf]) {
+	candidates.sort_by_cached_key(|path| {
+		(
+			Reverse(get_candidate_score(path)),
+			// When everything else is the same, the biggest exe is usually the game.
+			Reverse(fs::metadata(path).map_or(0, |metadata| metadata.len())),
+		)
+	});
+}
+
+// Finds the game for the given path, which can be the game exe,
+// a launcher that isn't the game exe itself, or the game folder.
+pub fn find_game(path: &Path) -> Result<FoundGame> {
+	if let Some(game) = create_game_from_path(path) {
+		return Ok(FoundGame {
+			game,
+			path: path.to_path_buf(),
+			other_candidates: Vec::default(),
+		});
+	}
+
+	let folder_path = if path.is_dir() {
+		path
+	} else {
+		paths::path_parent(path)?
+	};
+
+	let mut candidates = Vec::default();
+	find_candidates(folder_path, CANDIDATE_SEARCH_DEPTH, &mut candidates);
+	rank_candidates(&mut candidates);
+
+	let mut found_games: Vec<(PathBuf, InstalledGame)> = Vec::default();
+	for candidate in candidates {
+		if let Some(game) = create_game_from_path(&candidate) {
+			// Different exes can resolve to the same game exe (like Unreal launchers),
+			// so there's no point in offering both.
+			if found_games
+				.iter()
+				.all(|(_, found_game)| found_game.executable.path != game.executable.path)
+			{
+				found_games.push((candidate, game));
+			}
+		}
+	}
+
+	let mut found_games_iter = found_games.into_iter();
+	let (game_path, game) = found_games_iter
+		.next()
+		.ok_or_else(|| Error::FailedToGetGameFromPath(path.to_path_buf()))?;
 
+	Ok(FoundGame {
+		game,
+		path: game_path,
+		other_candidates: found_games_iter.map(|(candidate, _)| candidate).collect(),
+	})
+}
+
+pub fn add_game(path: &Path) -> Result {
 	let config_path = games_config_path()?;
 
 	let mut games_config = read_games_config(&config_path);
@@ -97,13 +249,31 @@ pub fn add_game(path: &Path) -> Result<InstalledGame> {
 
 	fs::write(config_path, serde_json::to_string_pretty(&games_config)?)?;
 
-	Ok(game)
+	Ok(())
+}
+
+pub fn set_game_overrides(path: &Path, overrides: &GameOverrides) -> Result {
+	let config_path = games_config_path()?;
+	let mut games_config = read_games_config(&config_path);
+
+	if overrides.is_empty() {
+		games_config.overrides.remove(path);
+	} else {
+		games_config
+			.overrides
+			.insert(path.to_path_buf(), overrides.clone());
+	}
+
+	fs::write(config_path, serde_json::to_string_pretty(&games_config)?)?;
+
+	Ok(())
 }
 
 pub fn remove_game(path: &Path) -> Result {
 	let config_path = games_config_path()?;
 	let mut games_config = read_games_config(&config_path);
 	games_config.paths.retain(|p| p != path);
+	games_config.overrides.remove(path);
 
 	fs::write(config_path, serde_json::to_string_pretty(&games_config)?)?;
 
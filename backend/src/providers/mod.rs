@@ -1,8 +1,10 @@
+pub mod directory_provider;
 pub mod epic_provider;
 pub mod gog_provider;
 pub mod itch_provider;
 pub mod manual_provider;
 pub mod provider;
 pub mod provider_command;
+pub mod provider_settings;
 pub mod steam_provider;
 pub mod xbox_provider;
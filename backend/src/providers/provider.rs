@@ -7,22 +7,28 @@ use std::{
 
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
-use log::error;
+use log::{
+	error,
+	info,
+};
 
 #[cfg(target_os = "windows")]
-use crate::providers::{
-	epic_provider::Epic,
-	gog_provider::Gog,
-	xbox_provider::Xbox,
-};
+use crate::providers::xbox_provider::Xbox;
 use crate::{
 	debug::LoggableInstant,
 	installed_game::InstalledGame,
 	owned_game::OwnedGame,
 	paths,
 	providers::{
+		directory_provider::Directory,
+		epic_provider::Epic,
+		gog_provider::Gog,
 		itch_provider::Itch,
 		manual_provider::Manual,
+		provider_settings::{
+			self,
+			ProviderSettings,
+		},
 		steam_provider::Steam,
 	},
 	remote_game::{
@@ -40,6 +46,7 @@ serializable_enum!(ProviderId {
 	Epic,
 	Gog,
 	Xbox,
+	Directory,
 });
 
 #[enum_dispatch]
@@ -48,9 +55,8 @@ pub enum Provider {
 	Steam,
 	Manual,
 	Itch,
-	#[cfg(target_os = "windows")]
+	Directory,
 	Epic,
-	#[cfg(target_os = "windows")]
 	Gog,
 	#[cfg(target_os = "windows")]
 	Xbox,
@@ -69,7 +75,7 @@ pub trait ProviderActions {
 pub trait ProviderStatic: ProviderActions {
 	const ID: &'static ProviderId;
 
-	fn new() -> Result<Self>
+	fn new(settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized;
 
@@ -136,20 +142,34 @@ pub trait ProviderStatic: ProviderActions {
 
 pub type Map = HashMap<String, Provider>;
 
-fn create_map_entry<TProvider: ProviderActions + ProviderStatic>() -> Result<(String, Provider)>
+fn create_map_entry<TProvider: ProviderActions + ProviderStatic>(
+	settings: &ProviderSettings,
+) -> Result<(String, Provider)>
 where
 	Provider: From<TProvider>,
 {
-	let mod_loader: Provider = TProvider::new()?.into();
+	let mod_loader: Provider = TProvider::new(settings)?.into();
 
 	Ok((TProvider::ID.to_string(), mod_loader))
 }
 
-fn add_entry<TProvider: ProviderActions + ProviderStatic>(map: &mut Map)
-where
+fn add_entry<TProvider: ProviderActions + ProviderStatic>(
+	map: &mut Map,
+	settings_map: &provider_settings::Map,
+) where
 	Provider: From<TProvider>,
 {
-	match create_map_entry::<TProvider>() {
+	let settings = provider_settings::get(settings_map, *TProvider::ID);
+
+	if !settings.enabled {
+		info!(
+			"Skipping provider ({}), since it's disabled.",
+			TProvider::ID
+		);
+		return;
+	}
+
+	match create_map_entry::<TProvider>(&settings) {
 		Ok((key, value)) => {
 			map.insert(key, value);
 		}
@@ -159,26 +179,32 @@ where
 
 pub fn get_map() -> Map {
 	let mut map = Map::new();
+	let settings = provider_settings::get_all();
 	let now = &mut Instant::now();
 
-	add_entry::<Steam>(&mut map);
+	add_entry::<Steam>(&mut map, &settings);
 	now.log_next("set up provider (Steam)");
 
-	add_entry::<Itch>(&mut map);
+	add_entry::<Itch>(&mut map, &settings);
 	now.log_next("set up provider (Itch)");
 
-	add_entry::<Manual>(&mut map);
+	add_entry::<Manual>(&mut map, &settings);
 	now.log_next("set up provider (Manual)");
 
-	#[cfg(target_os = "windows")]
-	{
-		add_entry::<Epic>(&mut map);
-		now.log_next("set up provider (Epic)");
+	add_entry::<Directory>(&mut map, &settings);
+	now.log_next("set up provider (Directory)");
 
-		add_entry::<Gog>(&mut map);
-		now.log_next("set up provider (Gog)");
+	// Epic and GOG can be read on any OS, but without a root path override
+	// they can only find their data through the Windows registry.
+	add_entry::<Epic>(&mut map, &settings);
+	now.log_next("set up provider (Epic)");
 
-		add_entry::<Xbox>(&mut map);
+	add_entry::<Gog>(&mut map, &settings);
+	now.log_next("set up provider (Gog)");
+
+	#[cfg(target_os = "windows")]
+	{
+		add_entry::<Xbox>(&mut map, &settings);
 		now.log_next("set up provider (Xbox)");
 	}
 	map
@@ -0,0 +1,96 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use super::provider::ProviderId;
+use crate::{
+	paths::app_data_path,
+	serializable_struct,
+	Result,
+};
+
+serializable_struct!(ProviderSettings {
+	#[serde(default = "default_enabled")]
+	pub enabled: bool,
+
+	// Overrides the folders where the provider looks for its data.
+	// Providers that only read from a single folder will use the first one.
+	#[serde(default)]
+	pub root_paths: Vec<PathBuf>,
+
+	// How many folder levels to look into when scanning the root paths for games.
+	// Only used by providers that scan folders, like the Directory provider.
+	#[serde(default)]
+	pub scan_depth: Option<u32>,
+});
+
+// Keys are the provider ids. Can't use ProviderId directly because tauri-specta doesn't support it.
+pub type Map = HashMap<String, ProviderSettings>;
+
+const fn default_enabled() -> bool {
+	true
+}
+
+impl Default for ProviderSettings {
+	fn default() -> Self {
+		Self {
+			enabled: default_enabled(),
+			root_paths: Vec::default(),
+			scan_depth: None,
+		}
+	}
+}
+
+impl ProviderSettings {
+	pub fn root_path(&self) -> Option<&Path> {
+		self.root_paths.first().map(PathBuf::as_path)
+	}
+}
+
+fn settings_path() -> Result<PathBuf> {
+	Ok(app_data_path()?.join("provider-settings.json"))
+}
+
+fn read_settings(settings_path: &Path) -> Result<Map> {
+	if !settings_path.is_file() {
+		return Ok(Map::default());
+	}
+
+	let json = fs::read_to_string(settings_path)?;
+	Ok(serde_json::from_str::<Map>(&json)?)
+}
+
+pub fn get_all() -> Map {
+	match settings_path().and_then(|path| read_settings(&path)) {
+		Ok(settings) => settings,
+		Err(err) => {
+			error!("Failed to read provider settings: {err}");
+			Map::default()
+		}
+	}
+}
+
+pub fn get(settings: &Map, provider_id: ProviderId) -> ProviderSettings {
+	settings
+		.get(&provider_id.to_string())
+		.cloned()
+		.unwrap_or_default()
+}
+
+pub fn set(provider_id: ProviderId, provider_settings: ProviderSettings) -> Result {
+	let path = settings_path()?;
+
+	let mut settings = read_settings(&path)?;
+	settings.insert(provider_id.to_string(), provider_settings);
+
+	fs::write(path, serde_json::to_string_pretty(&settings)?)?;
+
+	Ok(())
+}
@@ -14,6 +14,10 @@ use super::{
 		ProviderCommand,
 		ProviderCommandAction,
 	},
+	provider_settings::{
+		self,
+		ProviderSettings,
+	},
 };
 use crate::{
 	game_engines::game_engine::GameEngine,
@@ -55,11 +59,11 @@ pub struct Steam {
 impl ProviderStatic for Steam {
 	const ID: &'static ProviderId = &ProviderId::Steam;
 
-	fn new() -> Result<Self>
+	fn new(settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized,
 	{
-		let steam_dir = SteamDir::locate()?;
+		let steam_dir = get_steam_dir(settings)?;
 		let app_info_file = appinfo::read(steam_dir.path())?;
 		let remote_game_cache = Self::try_get_remote_game_cache();
 
@@ -282,6 +286,20 @@ impl ProviderActions for Steam {
 	}
 }
 
+fn get_steam_dir(settings: &ProviderSettings) -> Result<SteamDir> {
+	Ok(settings
+		.root_path()
+		.map_or_else(SteamDir::locate, SteamDir::from_dir)?)
+}
+
+// Finds the Steam folder in the same way the provider does, respecting the user's settings.
+pub fn locate_steam_dir() -> Result<SteamDir> {
+	get_steam_dir(&provider_settings::get(
+		&provider_settings::get_all(),
+		*Steam::ID,
+	))
+}
+
 pub fn get_start_command(
 	steam_launch: &SteamLaunchOption,
 	discriminator: &Option<String>,
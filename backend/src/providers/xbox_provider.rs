@@ -12,7 +12,10 @@ use winreg::{
 	RegKey,
 };
 
-use super::provider::ProviderId;
+use super::{
+	provider::ProviderId,
+	provider_settings::ProviderSettings,
+};
 use crate::{
 	installed_game::InstalledGame,
 	owned_game::OwnedGame,
@@ -31,7 +34,7 @@ pub struct Xbox {}
 impl ProviderStatic for Xbox {
 	const ID: &'static ProviderId = &ProviderId::Xbox;
 
-	fn new() -> Result<Self>
+	fn new(_settings: &ProviderSettings) -> Result<Self>
 	where
 		Self: Sized,
 	{
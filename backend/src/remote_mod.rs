@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
 use crate::{
-	game_mod::CommonModData,
-	mod_loaders::mod_database::ModDownload,
+	game_mod::{
+		CommonModData,
+		ModRequirement,
+	},
+	mod_loaders::mod_database::{
+		ModDownload,
+		ModGithubInfo,
+	},
 	serializable_struct,
 };
 
@@ -12,6 +18,11 @@ serializable_struct!(RemoteModData {
   pub source_code: String,
   pub description: String,
   pub latest_version: Option<ModDownload>,
+  pub dependencies: Vec<ModRequirement>,
+  pub conflicts: Vec<ModRequirement>,
+  pub github: Option<ModGithubInfo>,
+  // Name of the database source this mod came from.
+  pub source: String,
 });
 
 serializable_struct!(RemoteMod {
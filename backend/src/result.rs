@@ -30,6 +30,9 @@ pub enum Error {
 	#[error(transparent)]
 	Zip(#[from] zip::result::ZipError),
 
+	#[error(transparent)]
+	SevenZip(#[from] sevenz_rust::Error),
+
 	#[error(transparent)]
 	Tauri(#[from] tauri::Error),
 
@@ -54,6 +57,9 @@ pub enum Error {
 	#[error(transparent)]
 	HeaderToStr(#[from] reqwest::header::ToStrError),
 
+	#[error("Failed to verify signature: {0}")]
+	Minisign(#[from] minisign_verify::Error),
+
 	#[error("Invalid type `{0}` in binary vdf key/value pair")]
 	InvalidBinaryVdfType(u8),
 
@@ -63,12 +69,18 @@ pub enum Error {
 	#[error("Failed to find app data folder")]
 	AppDataNotFound(),
 
+	#[error("Failed to find the data folder for provider `{0}`. You can set its location in the provider settings.")]
+	ProviderDataNotFound(String),
+
 	#[error("Failed to parse path (possibly because is a non-UTF-8 string) `{0}`")]
 	PathParseFailure(PathBuf),
 
 	#[error("Failed to get folder parent for path `{0}`")]
 	PathParentNotFound(PathBuf),
 
+	#[error("Can't move mod data from `{0}` to `{1}`, since the target folder isn't empty")]
+	ModDataFolderNotEmpty(PathBuf, PathBuf),
+
 	#[error("Tried to read empty file `{0}`")]
 	EmptyFile(PathBuf),
 
@@ -104,6 +116,71 @@ pub enum Error {
 	#[error("Download not available for mod `{0}`")]
 	ModDownloadNotAvailable(String),
 
+	#[error("Version `{1}` of mod `{0}` hasn't been downloaded")]
+	ModVersionNotFound(String, String),
+
+	#[error("Download was cancelled: `{0}`")]
+	DownloadCancelled(String),
+
+	#[error("This is already being downloaded: `{0}`")]
+	DownloadInProgress(String),
+
+	#[error("Can't install mod `{0}`, because it needs `{1}`, which isn't available")]
+	ModDependencyNotAvailable(String, String),
+
+	#[error("Can't install these mods, because they depend on each other in a loop: {0}")]
+	ModDependencyCycle(String),
+
+	#[error("Can't install mods `{0}` and `{1}` together, because they conflict with each other")]
+	ModConflict(String, String),
+
+	#[error("Mod profile not found: `{0}`")]
+	ModProfileNotFound(String),
+
+	#[error("A mod profile with this name already exists: `{0}`")]
+	ModProfileAlreadyExists(String),
+
+	#[error("Archive `{0}` has an entry that would be extracted outside of the target folder, or is a symbolic link: `{1}`")]
+	UnsafeArchiveEntry(PathBuf, String),
+
+	#[error("Archive `{0}` isn't in a supported format (zip, 7z, tar, tar.gz, tar.xz or tar.zst)")]
+	UnsupportedArchiveFormat(PathBuf),
+
+	#[error("Archive `{0}` is too big to extract (limit is {1} bytes)")]
+	ArchiveTooLarge(PathBuf, u64),
+
+	#[error("Downloaded file `{0}` doesn't match what we expected ({1}). It might be corrupted or tampered with, so it won't be used.")]
+	DownloadVerificationFailed(PathBuf, String),
+
+	#[error("Invalid mod database entry `{0}`: {1}")]
+	InvalidModDatabaseEntry(String, String),
+
+	#[error("Invalid mod database source `{0}`: {1}")]
+	InvalidModDatabaseSource(String, String),
+
+	#[error(
+		"Mod database `{0}` isn't signed, so it can't be trusted. No mods from it will be shown."
+	)]
+	ModDatabaseSignatureMissing(String),
+
+	#[error("Mod database `{0}` has an invalid signature, so it can't be trusted. No mods from it will be shown. ({1})")]
+	ModDatabaseSignatureInvalid(String, String),
+
+	#[error("Mod database `{0}` uses schema version {1}, but this version of Rai Pal only understands up to version {2}. Please update Rai Pal.")]
+	ModDatabaseSchemaTooNew(String, u32, u32),
+
+	#[error("Mod id `{0}` can't be used, since it needs to work as a folder name")]
+	InvalidModId(String),
+
+	#[error("Mod `{0}` already exists. Importing it again would replace it.")]
+	ModAlreadyExists(String),
+
+	#[error("Can't import `{0}`, since it's already in the folder of the mod it would replace")]
+	ModImportFromTarget(PathBuf),
+
+	#[error("Couldn't find a mod in `{0}`. It needs a `plugins` or `patchers` folder, or a rai-pal-manifest.json file.")]
+	ModImportUnknownLayout(PathBuf),
+
 	#[error("Operation can't be completed without a `runnable` section in the mod manifest (rai-pal-manifest.json) `{0}`")]
 	RunnableManifestNotFound(String),
 }
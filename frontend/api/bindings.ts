@@ -11,7 +11,7 @@ declare global {
 const invoke = () => window.__TAURI_INVOKE__;
 
 export function dummyCommand() {
-    return invoke()<[InstalledGame, AppEvent, ProviderCommandAction]>("dummy_command")
+    return invoke()<[InstalledGame, AppEvent, ProviderCommandAction, DownloadProgress]>("dummy_command")
 }
 
 export function updateData() {
@@ -67,7 +67,7 @@ export function openModsFolder() {
 }
 
 export function addGame(path: string) {
-    return invoke()<null>("add_game", { path })
+    return invoke()<string[]>("add_game", { path })
 }
 
 export function removeGame(gameId: string) {
@@ -110,7 +110,111 @@ export function runProviderCommand(ownedGameId: string, commandAction: string) {
     return invoke()<null>("run_provider_command", { ownedGameId,commandAction })
 }
 
-export type Manifest = { version: string; runnable: RunnableModData | null; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null }
+export function getProviderSettings() {
+    return invoke()<{ [key: string]: ProviderSettings }>("get_provider_settings")
+}
+
+export function setProviderSettings(providerId: ProviderId, settings: ProviderSettings) {
+    return invoke()<null>("set_provider_settings", { providerId,settings })
+}
+
+export function setGameOverrides(gameId: string, overrides: GameOverrides) {
+    return invoke()<null>("set_game_overrides", { gameId,overrides })
+}
+
+export function getDiskUsage() {
+    return invoke()<DiskUsageReport>("get_disk_usage")
+}
+
+export function removeOrphanedModData(folderIds: string[]) {
+    return invoke()<DiskUsage[]>("remove_orphaned_mod_data", { folderIds })
+}
+
+export function cancelModDownload(modId: string) {
+    return invoke()<null>("cancel_mod_download", { modId })
+}
+
+export function restoreGameFiles(gameId: string) {
+    return invoke()<null>("restore_game_files", { gameId })
+}
+
+export function uninstallModLoader(gameId: string, modLoaderId: string) {
+    return invoke()<null>("uninstall_mod_loader", { gameId,modLoaderId })
+}
+
+export function setModEnabled(gameId: string, modId: string, enabled: boolean) {
+    return invoke()<null>("set_mod_enabled", { gameId,modId,enabled })
+}
+
+export function setLaunchVanilla(gameId: string, launchVanilla: boolean) {
+    return invoke()<null>("set_launch_vanilla", { gameId,launchVanilla })
+}
+
+export function getModProfiles(gameId: string) {
+    return invoke()<ModProfile[]>("get_mod_profiles", { gameId })
+}
+
+export function createModProfile(gameId: string, name: string) {
+    return invoke()<ModProfile>("create_mod_profile", { gameId,name })
+}
+
+export function renameModProfile(gameId: string, name: string, newName: string) {
+    return invoke()<null>("rename_mod_profile", { gameId,name,newName })
+}
+
+export function deleteModProfile(gameId: string, name: string) {
+    return invoke()<null>("delete_mod_profile", { gameId,name })
+}
+
+export function applyModProfile(gameId: string, name: string) {
+    return invoke()<ModProfileChanges>("apply_mod_profile", { gameId,name })
+}
+
+export function getModUpdates() {
+    return invoke()<ModUpdate[]>("get_mod_updates")
+}
+
+export function updateInstalledMods(gameId: string | null, modId: string | null) {
+    return invoke()<ModUpdateSummary>("update_installed_mods", { gameId,modId })
+}
+
+export function getModVersions(modId: string) {
+    return invoke()<ModVersion[]>("get_mod_versions", { modId })
+}
+
+export function installModVersion(gameId: string, modId: string, version: string) {
+    return invoke()<null>("install_mod_version", { gameId,modId,version })
+}
+
+export function pinModVersion(gameId: string, modId: string, version: string | null) {
+    return invoke()<null>("pin_mod_version", { gameId,modId,version })
+}
+
+export function downloadModRelease(modId: string, tag: string, assetName: string | null) {
+    return invoke()<null>("download_mod_release", { modId,tag,assetName })
+}
+
+export function getModChangelog(modId: string) {
+    return invoke()<ModRelease[]>("get_mod_changelog", { modId })
+}
+
+export function getModReleaseAssets(modId: string, tag: string) {
+    return invoke()<string[]>("get_mod_release_assets", { modId,tag })
+}
+
+export function getModDatabaseSources() {
+    return invoke()<ModDatabaseSource[]>("get_mod_database_sources")
+}
+
+export function setModDatabaseSources(sources: ModDatabaseSource[]) {
+    return invoke()<null>("set_mod_database_sources", { sources })
+}
+
+export function importMod(path: string, overwrite: boolean) {
+    return invoke()<string>("import_mod", { path,overwrite })
+}
+
+export type Manifest = { id: string | null; version: string; runnable: RunnableModData | null; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null; dependencies: ModRequirement[]; conflicts: ModRequirement[] }
 export type CommonModData = { id: string; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null; loaderId: string }
 export type GameEngineBrand = "Unity" | "Unreal" | "Godot" | "GameMaker"
 export type GameMode = "VR" | "Flat"
@@ -118,21 +222,38 @@ export type ModKind = "Installable" | "Runnable"
 export type GameEngine = { brand: GameEngineBrand; version: GameEngineVersion | null }
 export type GameEngineVersion = { major: number; minor: number; patch: number; suffix: string | null; display: string }
 export type OwnedGame = { id: string; provider: ProviderId; name: string; osList: OperatingSystem[]; releaseDate: BigInt | null; thumbnailUrl: string | null; gameMode: GameMode | null; providerCommands: { [key: string]: ProviderCommand } }
-export type AppEvent = "SyncInstalledGames" | "SyncOwnedGames" | "SyncRemoteGames" | "SyncModLoaders" | "SyncLocalMods" | "SyncRemoteMods" | "ExecutedProviderCommand" | "GameAdded" | "GameRemoved" | "Error"
+export type AppEvent = "SyncInstalledGames" | "SyncOwnedGames" | "SyncRemoteGames" | "SyncModLoaders" | "SyncLocalMods" | "SyncRemoteMods" | "ExecutedProviderCommand" | "GameAdded" | "GameRemoved" | "ModDownloadProgress" | "ModUpdatesFinished" | "Error"
 export type LocalMod = { data: LocalModData; common: CommonModData }
 export type RemoteGame = { id: string; engine: GameEngine | null; uevrScore: UevrScore | null; skipCache: boolean }
-export type InstalledGame = { id: string; name: string; provider: ProviderId; executable: GameExecutable; installedModVersions: { [key: string]: string }; discriminator: string | null; thumbnailUrl: string | null; ownedGameId: string | null; startCommand: ProviderCommand | null }
+export type InstalledGame = { id: string; name: string; provider: ProviderId; executable: GameExecutable; installedModVersions: { [key: string]: InstalledModVersion }; launchVanilla: boolean; discriminator: string | null; thumbnailUrl: string | null; ownedGameId: string | null; startCommand: ProviderCommand | null; overrides: GameOverrides }
 export type RunnableModData = { path: string; args: string[] }
-export type RemoteModData = { title: string; author: string; sourceCode: string; description: string; latestVersion: ModDownload | null }
+export type RemoteModData = { title: string; author: string; sourceCode: string; description: string; latestVersion: ModDownload | null; dependencies: ModRequirement[]; conflicts: ModRequirement[]; github: ModGithubInfo | null; source: string }
 export type LocalModData = { path: string; manifest: Manifest | null }
 export type ModLoaderData = { id: string; path: string; kind: ModKind }
 export type UnityScriptingBackend = "Il2Cpp" | "Mono"
 export type GameExecutable = { path: string; name: string; engine: GameEngine | null; architecture: Architecture | null; operatingSystem: OperatingSystem | null; scriptingBackend: UnityScriptingBackend | null }
 export type ProviderCommandAction = "Install" | "ShowInLibrary" | "ShowInStore" | "Start" | "OpenInBrowser"
-export type ProviderId = "Steam" | "Manual" | "Itch" | "Epic" | "Gog" | "Xbox"
+export type ProviderId = "Steam" | "Manual" | "Itch" | "Epic" | "Gog" | "Xbox" | "Directory"
 export type RemoteMod = { common: CommonModData; data: RemoteModData }
-export type ModDownload = { id: string; url: string; root: string | null; runnable: RunnableModData | null }
+export type ModDownload = { id: string; url: string; root: string | null; runnable: RunnableModData | null; sha256: string | null; size: BigInt | null }
 export type UevrScore = "A" | "B" | "C" | "D" | "E"
 export type OperatingSystem = "Linux" | "Windows"
 export type ProviderCommand = { String: string } | { Path: [string, string[]] }
 export type Architecture = "X64" | "X86"
+export type ProviderSettings = { enabled: boolean; rootPaths: string[]; scanDepth: number | null }
+export type GameOverrides = { name: string | null; thumbnailUrl: string | null; engineBrand: GameEngineBrand | null; engineVersion: string | null; scriptingBackend: UnityScriptingBackend | null; architecture: Architecture | null; operatingSystem: OperatingSystem | null }
+export type DiskUsageReport = { games: { [key: string]: DiskUsage }; modLoaders: { [key: string]: DiskUsage }; downloads: { [key: string]: DiskUsage }; modVersions: { [key: string]: DiskUsage }; orphanedGames: { [key: string]: DiskUsage }; orphanedGamesWithJournal: { [key: string]: DiskUsage } }
+export type DiskUsage = { path: string; size: BigInt }
+export type DownloadProgress = { id: string; downloadedBytes: BigInt; totalBytes: BigInt | null }
+export type InstalledModVersion = { version: string; enabled: boolean; pinnedVersion: string | null }
+export type ModProfile = { name: string; mods: { [key: string]: string }; launchVanilla: boolean }
+export type ModProfileChanges = { installed: string[]; enabled: string[]; disabled: string[]; unavailable: string[]; versionMismatches: string[] }
+export type ModRequirement = { id: string; version: string | null }
+export type ModUpdate = { gameId: string; modId: string; installedVersion: string; latestVersion: string; needsDownload: boolean }
+export type ModUpdateFailure = { gameId: string; modId: string; error: string }
+export type ModUpdateSummary = { updated: ModUpdate[]; failed: ModUpdateFailure[] }
+export type ModVersion = { version: string; path: string }
+export type ModGithubInfo = { user: string; repo: string; assetName: string; root: string | null; runnable: RunnableModData | null; tag: string | null; sha256: string | null; size: BigInt | null }
+export type ModRelease = { tag: string; title: string; updatedAt: string; notes: string }
+export type ModDatabaseSource = { name: string; url: string; kind: ModDatabaseSourceKind; unityBackend: UnityScriptingBackend | null }
+export type ModDatabaseSourceKind = "RaiPal" | "Thunderstore"
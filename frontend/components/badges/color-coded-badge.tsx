@@ -105,6 +105,7 @@ export const ProviderBadge = CreateColorCodedBadge<ProviderId>(
 		Gog: "violet",
 		Xbox: "green",
 		Itch: "teal",
+		Directory: "orange",
 	},
 	providerFilterOptions,
 );
@@ -32,7 +32,8 @@ type Props = {
 };
 
 export function GameModRow(props: Props) {
-	const installedVersion = props.game.installedModVersions[props.mod.common.id];
+	const installedVersion =
+		props.game.installedModVersions[props.mod.common.id]?.version;
 	const isInstalledModOutdated = getIsOutdated(
 		installedVersion,
 		props.mod.remote?.latestVersion?.id,
@@ -54,7 +54,7 @@ const provider: TableColumnBase<ProcessedOwnedGame, ProviderId> = {
 	getSortValue: (game) => game.provider,
 	getFilterValue: (game) => game.provider,
 	filterOptions: providerFilterOptions,
-	unavailableValues: ["Manual", "Xbox"],
+	unavailableValues: ["Manual", "Xbox", "Directory"],
 	renderCell: (game) => (
 		<Table.Td>
 			<ProviderBadge value={game.provider} />
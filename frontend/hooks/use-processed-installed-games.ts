@@ -47,7 +47,7 @@ export function useProcessedInstalledGames() {
 					Object.entries(installedGame.installedModVersions).findIndex(
 						([modId, installedVersion]) =>
 							getIsOutdated(
-								installedVersion,
+								installedVersion.version,
 								mods[modId]?.remote?.latestVersion?.id,
 							),
 					) !== -1,
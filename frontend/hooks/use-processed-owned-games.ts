@@ -36,6 +36,7 @@ export function useProcessedOwnedGames() {
 			Gog: {},
 			Xbox: {},
 			Itch: {},
+			Directory: {},
 		};
 
 		for (const installedGame of Object.values(installedGames)) {
@@ -15,4 +15,5 @@ export const providerFilterOptions: FilterOption<ProviderId>[] = [
 	{ label: "Xbox", value: "Xbox" },
 	{ label: "Itch", value: "Itch" },
 	{ label: "Manual", value: "Manual" },
+	{ label: "Directory", value: "Directory" },
 ];